@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+use glutin::surface::Rect;
+
+/// Track the damage of the recent frames to repaint only the regions that
+/// changed since the back buffer was presented.
+///
+/// The region is computed from the buffer age, as returned by
+/// [`GlSurface::buffer_age`], so it could be passed to
+/// `swap_buffers_with_damage`.
+///
+/// # Example
+/// ```no_run
+/// use glutin_winit::DamageTracker;
+/// # use glutin::prelude::*;
+/// # use glutin::surface::{Rect, Surface, WindowSurface};
+/// # let gl_surface: Surface<WindowSurface> = unimplemented!();
+///
+/// let mut damage_tracker = DamageTracker::new(4);
+/// let damage = [Rect::new(0, 0, 64, 64)];
+/// match damage_tracker.repaint_region(gl_surface.buffer_age(), &damage) {
+///     Some(region) => {
+///         // Repaint only the `region` and pass it to `swap_buffers_with_damage`.
+///     },
+///     None => {
+///         // Repaint the whole surface.
+///     },
+/// }
+/// ```
+///
+/// [`GlSurface::buffer_age`]: glutin::surface::GlSurface::buffer_age
+#[derive(Debug, Clone)]
+pub struct DamageTracker {
+    /// The damage of the recent frames, starting from the latest one.
+    frames: VecDeque<Vec<Rect>>,
+    max_history: usize,
+}
+
+impl DamageTracker {
+    /// Create the tracker remembering the damage of up to `max_history`
+    /// frames.
+    ///
+    /// The buffers older than that are repainted entirely.
+    pub fn new(max_history: usize) -> Self {
+        Self { frames: VecDeque::with_capacity(max_history), max_history }
+    }
+
+    /// Record the `damage` of the frame being rendered into the buffer of the
+    /// given `buffer_age` and return the region which must be repainted.
+    ///
+    /// The region is the union of the `damage` and the damage of the frames
+    /// presented since the buffer was. [`None`] is returned when the whole
+    /// surface must be repainted, that is when the `buffer_age` is zero,
+    /// meaning that the buffer contents are undefined, or exceeds the
+    /// remembered history.
+    pub fn repaint_region(&mut self, buffer_age: u32, damage: &[Rect]) -> Option<Vec<Rect>> {
+        let missed_frames = buffer_age as usize;
+        let region = if missed_frames == 0 || missed_frames - 1 > self.frames.len() {
+            None
+        } else {
+            let mut region = damage.to_vec();
+            for rect in self.frames.iter().take(missed_frames - 1).flatten() {
+                if !region.contains(rect) {
+                    region.push(*rect);
+                }
+            }
+            Some(region)
+        };
+
+        if self.max_history > 0 {
+            self.frames.truncate(self.max_history - 1);
+            self.frames.push_front(damage.to_vec());
+        }
+
+        region
+    }
+
+    /// Forget the damage history, so the next frame is repainted entirely,
+    /// like after the surface was resized.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
@@ -8,11 +8,15 @@
 #![deny(missing_docs)]
 #![cfg_attr(clippy, deny(warnings))]
 
+mod damage;
 mod event_loop;
+mod pacing;
 mod window;
 
+pub use damage::DamageTracker;
 use event_loop::GlutinEventLoop;
-pub use window::GlWindow;
+pub use pacing::FramePacer;
+pub use window::{GlWindow, GlWindows};
 
 use std::error::Error;
 
@@ -68,6 +72,9 @@ impl DisplayBuilder {
     }
 
     /// The preference in picking the configuration.
+    ///
+    /// Use [`ApiPreference::OnlyEgl`] or [`ApiPreference::OnlyNative`] to
+    /// force a specific backend.
     pub fn with_preference(mut self, preference: ApiPreference) -> Self {
         self.preference = preference;
         self
@@ -173,12 +180,16 @@ fn create_display(
         ApiPreference::FallbackEgl => {
             DisplayApiPreference::GlxThenEgl(Box::new(register_xlib_error_hook))
         },
+        ApiPreference::OnlyEgl => DisplayApiPreference::Egl,
+        ApiPreference::OnlyNative => DisplayApiPreference::Glx(Box::new(register_xlib_error_hook)),
     };
 
     #[cfg(all(wgl_backend, egl_backend))]
     let _preference = match _api_preference {
         ApiPreference::PreferEgl => DisplayApiPreference::EglThenWgl(_raw_window_handle),
         ApiPreference::FallbackEgl => DisplayApiPreference::WglThenEgl(_raw_window_handle),
+        ApiPreference::OnlyEgl => DisplayApiPreference::Egl,
+        ApiPreference::OnlyNative => DisplayApiPreference::Wgl(_raw_window_handle),
     };
 
     let handle = event_loop.glutin_display_handle()?.as_raw();
@@ -202,8 +213,8 @@ pub fn finalize_window(
     }
 
     #[cfg(x11_platform)]
-    let attributes = if let Some(x11_visual) = gl_config.x11_visual() {
-        attributes.with_x11_visual(x11_visual.visual_id() as _)
+    let attributes = if let Some(visual_id) = gl_config.x11_visual_id() {
+        attributes.with_x11_visual(visual_id as _)
     } else {
         attributes
     };
@@ -216,6 +227,9 @@ pub fn finalize_window(
 ///
 /// To learn about platform differences the [`DisplayApiPreference`] variants.
 ///
+/// When only one backend is available on the platform, it's used regardless of
+/// the preference.
+///
 /// [`DisplayApiPreference`]: glutin::display::DisplayApiPreference
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ApiPreference {
@@ -228,4 +242,15 @@ pub enum ApiPreference {
     /// [`Self::PreferEgl`] if you don't care about missing EGL features.
     #[default]
     FallbackEgl,
+
+    /// Use only `EGL` without falling back to the system provider.
+    ///
+    /// This is useful to pin the backend, for example in tests.
+    OnlyEgl,
+
+    /// Use only the system provider like `GLX` and `WGL` without falling back
+    /// to `EGL`.
+    ///
+    /// This is useful to pin the backend, for example in tests.
+    OnlyNative,
 }
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use winit::window::Window;
+
+/// Detect the frames which likely missed the vertical blank, so the
+/// application could lower the quality when it's falling behind.
+///
+/// The pacer measures the time between the swaps with the system clock and
+/// compares it against the refresh interval of the monitor, thus it's only
+/// meaningful when the swap interval is synchronized to the vertical blank.
+///
+/// # Example
+/// ```no_run
+/// use glutin_winit::FramePacer;
+/// # use glutin::prelude::*;
+/// # use glutin::surface::{Surface, WindowSurface};
+/// # let winit_window: winit::window::Window = unimplemented!();
+/// # let gl_surface: Surface<WindowSurface> = unimplemented!();
+/// # let gl_context: glutin::context::PossiblyCurrentContext = unimplemented!();
+///
+/// let mut frame_pacer = FramePacer::for_window(&winit_window);
+/// let buffer_age = gl_surface.buffer_age();
+/// // Draw.
+/// gl_surface.swap_buffers(&gl_context).unwrap();
+/// if frame_pacer.record_swap(buffer_age) {
+///     // Reduce the quality of the next frames.
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    frame_interval: Duration,
+    last_swap: Option<Instant>,
+    missed_frames: u64,
+}
+
+impl FramePacer {
+    /// The refresh interval used when the monitor doesn't report it.
+    const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+    /// Create the pacer for the monitor with the given refresh interval.
+    pub fn new(frame_interval: Duration) -> Self {
+        Self { frame_interval, last_swap: None, missed_frames: 0 }
+    }
+
+    /// Create the pacer for the refresh rate of the current monitor of the
+    /// `window`, falling back to 60Hz when it's unknown.
+    pub fn for_window(window: &Window) -> Self {
+        let frame_interval = window
+            .current_monitor()
+            .and_then(|monitor| monitor.refresh_rate_millihertz())
+            .filter(|&millihertz| millihertz != 0)
+            .map(|millihertz| Duration::from_secs_f64(1000. / millihertz as f64))
+            .unwrap_or(Self::DEFAULT_FRAME_INTERVAL);
+        Self::new(frame_interval)
+    }
+
+    /// The refresh interval the swaps are compared against.
+    pub fn frame_interval(&self) -> Duration {
+        self.frame_interval
+    }
+
+    /// Record the swap of the frame rendered into the buffer of the given
+    /// `buffer_age`, returning `true` when the frame likely missed the
+    /// vertical blank.
+    ///
+    /// The frame is considered late when more than one and a half refresh
+    /// intervals passed since the previous swap. The frames rendered into the
+    /// new buffers, that is with zero `buffer_age`, are never reported, since
+    /// the swap chain was just recreated, e.g. due to resize.
+    pub fn record_swap(&mut self, buffer_age: u32) -> bool {
+        let now = Instant::now();
+        let missed = buffer_age != 0
+            && self
+                .last_swap
+                .is_some_and(|last_swap| now - last_swap > self.frame_interval * 3 / 2);
+        self.last_swap = Some(now);
+        self.missed_frames += missed as u64;
+        missed
+    }
+
+    /// The number of frames reported as late by [`Self::record_swap`].
+    pub fn missed_frames(&self) -> u64 {
+        self.missed_frames
+    }
+
+    /// Forget the last swap, so the next frame isn't compared against it, like
+    /// after the application was idle.
+    pub fn reset(&mut self) {
+        self.last_swap = None;
+    }
+}
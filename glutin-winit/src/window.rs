@@ -1,16 +1,47 @@
+use std::collections::HashMap;
+use std::error::Error;
 use std::num::NonZeroU32;
 
-use glutin::context::PossiblyCurrentContext;
+use glutin::config::Config;
+use glutin::context::{PossiblyCurrentContext, PossiblyCurrentGlContext};
+use glutin::display::{GetGlDisplay, GlDisplay};
+use glutin::error::{ErrorKind, Result as GlResult};
 use glutin::surface::{
     GlSurface, ResizeableSurface, Surface, SurfaceAttributes, SurfaceAttributesBuilder,
     SurfaceTypeTrait, WindowSurface,
 };
-use raw_window_handle::{HandleError, HasWindowHandle};
-use winit::window::Window;
+use raw_window_handle::{HandleError, HasWindowHandle, RawWindowHandle};
+use winit::window::{Window, WindowId};
 
 /// [`Window`] extensions for working with [`glutin`] surfaces.
 pub trait GlWindow {
-    /// Build the surface attributes suitable to create a window surface.
+    /// The size of the surface backing the window in physical pixels, or
+    /// [`None`] when either dimension is zero.
+    ///
+    /// The physical size accounts for the scale factor, so on HiDPI displays
+    /// it's larger than the logical size of the window. It should be used to
+    /// resize the surface and the viewport on both [`WindowEvent::Resized`]
+    /// and [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use glutin_winit::GlWindow;
+    /// # let winit_window: winit::window::Window = unimplemented!();
+    ///
+    /// if let Some((width, height)) = winit_window.surface_size() {
+    ///     // Update the viewport.
+    /// }
+    /// ```
+    ///
+    /// [`WindowEvent::Resized`]: winit::event::WindowEvent::Resized
+    /// [`WindowEvent::ScaleFactorChanged`]: winit::event::WindowEvent::ScaleFactorChanged
+    fn surface_size(&self) -> Option<(NonZeroU32, NonZeroU32)>;
+
+    /// Build the surface attributes suitable to create a window surface, sized
+    /// with [`Self::surface_size`].
+    ///
+    /// To use a different size, where the platform allows it, pass it to
+    /// [`SurfaceAttributesBuilder::build`] directly.
     ///
     /// # Panics
     /// Panics if either window inner dimension is zero.
@@ -27,7 +58,8 @@ pub trait GlWindow {
         builder: SurfaceAttributesBuilder<WindowSurface>,
     ) -> Result<SurfaceAttributes<WindowSurface>, HandleError>;
 
-    /// Resize the surface to the window inner size.
+    /// Resize the surface to the [`Self::surface_size`], which should be done
+    /// when the window is resized or its scale factor changes.
     ///
     /// No-op if either window size is zero.
     ///
@@ -45,14 +77,41 @@ pub trait GlWindow {
         surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
         context: &PossiblyCurrentContext,
     );
+
+    /// Create the window surface with the `config` unless the `surface` was
+    /// already created for the same native window, returning `true` when the
+    /// surface was recreated.
+    ///
+    /// The `surface` is stored together with the raw window handle it was
+    /// created for, so spurious `resumed` events don't recreate it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use glutin_winit::GlWindow;
+    /// # let winit_window: winit::window::Window = unimplemented!();
+    /// # let gl_config: glutin::config::Config = unimplemented!();
+    ///
+    /// let mut gl_surface = None;
+    /// winit_window.rebuild_surface_if_changed(&gl_config, &mut gl_surface).unwrap();
+    /// ```
+    fn rebuild_surface_if_changed(
+        &self,
+        config: &Config,
+        surface: &mut Option<(RawWindowHandle, Surface<WindowSurface>)>,
+    ) -> Result<bool, Box<dyn Error>>;
 }
 
 impl GlWindow for Window {
+    fn surface_size(&self) -> Option<(NonZeroU32, NonZeroU32)> {
+        // The inner size is in physical pixels already.
+        self.inner_size().non_zero()
+    }
+
     fn build_surface_attributes(
         &self,
         builder: SurfaceAttributesBuilder<WindowSurface>,
     ) -> Result<SurfaceAttributes<WindowSurface>, HandleError> {
-        let (w, h) = self.inner_size().non_zero().expect("invalid zero inner size");
+        let (w, h) = self.surface_size().expect("invalid zero inner size");
         let handle = self.window_handle()?.as_raw();
         Ok(builder.build(handle, w, h))
     }
@@ -62,10 +121,157 @@ impl GlWindow for Window {
         surface: &Surface<impl SurfaceTypeTrait + ResizeableSurface>,
         context: &PossiblyCurrentContext,
     ) {
-        if let Some((w, h)) = self.inner_size().non_zero() {
+        if let Some((w, h)) = self.surface_size() {
             surface.resize(context, w, h)
         }
     }
+
+    fn rebuild_surface_if_changed(
+        &self,
+        config: &Config,
+        surface: &mut Option<(RawWindowHandle, Surface<WindowSurface>)>,
+    ) -> Result<bool, Box<dyn Error>> {
+        let raw_window_handle = self.window_handle()?.as_raw();
+        if matches!(surface, Some((handle, _)) if *handle == raw_window_handle) {
+            return Ok(false);
+        }
+
+        // Drop the old surface before creating the new one.
+        *surface = None;
+
+        let attrs = self.build_surface_attributes(Default::default())?;
+        let gl_surface = unsafe { config.display().create_window_surface(config, &attrs)? };
+        *surface = Some((raw_window_handle, gl_surface));
+
+        Ok(true)
+    }
+}
+
+/// A set of windows with their surfaces, which are rendered with a single
+/// context.
+///
+/// # Example
+/// ```no_run
+/// use glutin_winit::GlWindows;
+/// # use glutin::prelude::*;
+/// # let (gl_config, gl_context): (glutin::config::Config, glutin::context::PossiblyCurrentContext) = unimplemented!();
+/// # let (first, second): (winit::window::Window, winit::window::Window) = unimplemented!();
+///
+/// let mut windows = GlWindows::new();
+/// let first = windows.insert_window(&gl_config, first).unwrap();
+/// let second = windows.insert_window(&gl_config, second).unwrap();
+///
+/// for window_id in [first, second] {
+///     windows.make_current(window_id, &gl_context).unwrap();
+///     // Draw.
+///     windows.swap_buffers(window_id, &gl_context).unwrap();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct GlWindows {
+    // The surface goes first, so it's dropped before its window.
+    windows: HashMap<WindowId, (Surface<WindowSurface>, Window)>,
+}
+
+impl GlWindows {
+    /// Create an empty set of windows.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create the window surface for the `window` with the `config` and start
+    /// tracking them.
+    ///
+    /// The previously tracked window with the same id is replaced.
+    pub fn insert_window(
+        &mut self,
+        config: &Config,
+        window: Window,
+    ) -> Result<WindowId, Box<dyn Error>> {
+        let attrs = window.build_surface_attributes(Default::default())?;
+        let surface = unsafe { config.display().create_window_surface(config, &attrs)? };
+        Ok(self.insert(surface, window))
+    }
+
+    /// Start tracking the `window` with its `surface`.
+    ///
+    /// The previously tracked window with the same id is replaced, dropping
+    /// its surface before the window.
+    pub fn insert(&mut self, surface: Surface<WindowSurface>, window: Window) -> WindowId {
+        let window_id = window.id();
+        self.windows.insert(window_id, (surface, window));
+        window_id
+    }
+
+    /// Stop tracking the window, returning its surface with it.
+    ///
+    /// The surface must be dropped before the window, and must not be current
+    /// when it's dropped.
+    pub fn remove(&mut self, window_id: WindowId) -> Option<(Surface<WindowSurface>, Window)> {
+        self.windows.remove(&window_id)
+    }
+
+    /// Get the surface of the window and the window.
+    pub fn get(&self, window_id: WindowId) -> Option<(&Surface<WindowSurface>, &Window)> {
+        self.windows.get(&window_id).map(|(surface, window)| (surface, window))
+    }
+
+    /// Iterate over all the tracked windows with their surfaces.
+    pub fn iter(&self) -> impl Iterator<Item = (&Surface<WindowSurface>, &Window)> {
+        self.windows.values().map(|(surface, window)| (surface, window))
+    }
+
+    /// The number of tracked windows.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Returns `true` when no windows are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Make the surface of the window current with the `context`.
+    ///
+    /// [`ErrorKind::NotFound`] is returned when the window is not tracked.
+    pub fn make_current(
+        &self,
+        window_id: WindowId,
+        context: &PossiblyCurrentContext,
+    ) -> GlResult<()> {
+        let (surface, _) = self.get(window_id).ok_or(ErrorKind::NotFound)?;
+        context.make_current(surface)
+    }
+
+    /// Swap the buffers of the window surface.
+    ///
+    /// [`ErrorKind::NotFound`] is returned when the window is not tracked.
+    pub fn swap_buffers(
+        &self,
+        window_id: WindowId,
+        context: &PossiblyCurrentContext,
+    ) -> GlResult<()> {
+        let (surface, _) = self.get(window_id).ok_or(ErrorKind::NotFound)?;
+        surface.swap_buffers(context)
+    }
+
+    /// Resize the surface of the window to its physical size, which should be
+    /// called on [`WindowEvent::Resized`] and
+    /// [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// The surface is made current with the `context` first, since some
+    /// platforms require that.
+    ///
+    /// [`WindowEvent::Resized`]: winit::event::WindowEvent::Resized
+    /// [`WindowEvent::ScaleFactorChanged`]: winit::event::WindowEvent::ScaleFactorChanged
+    pub fn resize(&self, window_id: WindowId, context: &PossiblyCurrentContext) -> GlResult<()> {
+        let (surface, window) = self.get(window_id).ok_or(ErrorKind::NotFound)?;
+        if !surface.is_current(context) {
+            context.make_current(surface)?;
+        }
+        window.resize_surface(surface, context);
+        Ok(())
+    }
 }
 
 /// [`winit::dpi::PhysicalSize<u32>`] non-zero extensions.
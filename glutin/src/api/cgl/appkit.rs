@@ -73,6 +73,12 @@ extern_methods!(
             param: NSOpenGLContextParameter,
         );
 
+        #[method(currentVirtualScreen)]
+        pub(crate) fn currentVirtualScreen(&self) -> GLint;
+
+        #[method(setCurrentVirtualScreen:)]
+        pub(crate) unsafe fn setCurrentVirtualScreen(&self, screen: GLint);
+
         #[method(CGLContextObj)]
         pub(crate) fn CGLContextObj(&self) -> *mut CGLContextObj;
     }
@@ -114,5 +120,8 @@ extern_methods!(
             param: NSOpenGLPixelFormatAttribute,
             screen: GLint,
         );
+
+        #[method(numberOfVirtualScreens)]
+        pub(crate) fn numberOfVirtualScreens(&self) -> GLint;
     }
 );
@@ -9,15 +9,16 @@ use objc2_app_kit::{
     NSOpenGLPFAAccelerated, NSOpenGLPFAAllowOfflineRenderers, NSOpenGLPFAAlphaSize,
     NSOpenGLPFAColorFloat, NSOpenGLPFAColorSize, NSOpenGLPFADepthSize, NSOpenGLPFADoubleBuffer,
     NSOpenGLPFAMinimumPolicy, NSOpenGLPFAMultisample, NSOpenGLPFAOpenGLProfile,
-    NSOpenGLPFASampleBuffers, NSOpenGLPFASamples, NSOpenGLPFAStencilSize, NSOpenGLPFAStereo,
-    NSOpenGLPFATripleBuffer, NSOpenGLPixelFormatAttribute, NSOpenGLProfileVersion3_2Core,
-    NSOpenGLProfileVersion4_1Core, NSOpenGLProfileVersionLegacy,
+    NSOpenGLPFARendererID, NSOpenGLPFASampleBuffers, NSOpenGLPFASamples, NSOpenGLPFAStencilSize,
+    NSOpenGLPFAStereo, NSOpenGLPFATripleBuffer, NSOpenGLPixelFormatAttribute,
+    NSOpenGLProfileVersion3_2Core, NSOpenGLProfileVersion4_1Core, NSOpenGLProfileVersionLegacy,
 };
 
 use crate::config::{
-    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, RawConfig,
+    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, NativeVisual,
+    RawConfig,
 };
-use crate::display::GetGlDisplay;
+use crate::display::{GetGlDisplay, GpuPreference};
 use crate::error::{ErrorKind, Result};
 use crate::private::Sealed;
 
@@ -35,8 +36,11 @@ impl Display {
         // We use minimum to follow behavior of other platforms here.
         attrs.push(NSOpenGLPFAMinimumPolicy);
 
-        // Allow offline renderers.
-        attrs.push(NSOpenGLPFAAllowOfflineRenderers);
+        // Allow offline renderers, unless the discrete GPU is explicitly requested.
+        if template.allow_offline_renderers && self.gpu_preference != GpuPreference::HighPerformance
+        {
+            attrs.push(NSOpenGLPFAAllowOfflineRenderers);
+        }
 
         // Color.
         match template.color_buffer_type {
@@ -124,10 +128,27 @@ impl Display {
         });
         let config = Config { inner };
 
-        Ok(Box::new(iter::once(config)))
+        Ok(Box::new(iter::once(config).filter(move |config| template.within_max_sizes(config))))
     }
 }
 
+/// The virtual screen of the [`Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VirtualScreen {
+    /// The index of the virtual screen in the config.
+    pub index: i32,
+
+    /// The renderer ID, like `kCGLRendererIntelHD4000ID`.
+    ///
+    /// The ID identifies the renderer, not the particular GPU, so two identical
+    /// GPUs will have the same ID. Mask it with `kCGLRendererIDMatchingMask`
+    /// to compare against the `kCGLRenderer*ID` constants.
+    pub renderer_id: u32,
+
+    /// Whether the renderer is hardware accelerated.
+    pub accelerated: bool,
+}
+
 /// A wrapper around NSOpenGLPixelFormat.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Config {
@@ -135,15 +156,35 @@ pub struct Config {
 }
 
 impl Config {
+    /// The virtual screens of the config, each of them backed by a single
+    /// renderer.
+    ///
+    /// The virtual screen could be selected for the context with
+    /// [`PossiblyCurrentContext::set_virtual_screen`] to target a particular
+    /// GPU.
+    ///
+    /// [`PossiblyCurrentContext::set_virtual_screen`]: super::context::PossiblyCurrentContext::set_virtual_screen
+    #[allow(deprecated)]
+    pub fn virtual_screens(&self) -> Vec<VirtualScreen> {
+        (0..self.inner.raw.numberOfVirtualScreens())
+            .map(|index| VirtualScreen {
+                index,
+                renderer_id: self.raw_attribute_for_screen(NSOpenGLPFARendererID, index) as u32,
+                accelerated: self.raw_attribute_for_screen(NSOpenGLPFAAccelerated, index) != 0,
+            })
+            .collect()
+    }
+
     fn raw_attribute(&self, attrib: NSOpenGLPixelFormatAttribute) -> i32 {
+        // They do differ per monitor and require context. Which is kind of insane, but
+        // whatever. Zero is a primary monitor.
+        self.raw_attribute_for_screen(attrib, 0)
+    }
+
+    fn raw_attribute_for_screen(&self, attrib: NSOpenGLPixelFormatAttribute, screen: i32) -> i32 {
         unsafe {
             let mut value = 0;
-            self.inner.raw.getValues_forAttribute_forVirtualScreen(
-                &mut value, attrib,
-                // They do differ per monitor and require context. Which is kind of insane, but
-                // whatever. Zero is a primary monitor.
-                0,
-            );
+            self.inner.raw.getValues_forAttribute_forVirtualScreen(&mut value, attrib, screen);
             value
         }
     }
@@ -196,14 +237,34 @@ impl GlConfig for Config {
         self.raw_attribute(NSOpenGLPFASamples) as u8
     }
 
+    fn num_sample_buffers(&self) -> u8 {
+        self.raw_attribute(NSOpenGLPFASampleBuffers) as u8
+    }
+
     fn config_surface_types(&self) -> ConfigSurfaceTypes {
         ConfigSurfaceTypes::WINDOW
     }
 
+    fn max_pbuffer_width(&self) -> u32 {
+        0
+    }
+
+    fn max_pbuffer_height(&self) -> u32 {
+        0
+    }
+
+    fn max_pbuffer_pixels(&self) -> u32 {
+        0
+    }
+
     fn supports_transparency(&self) -> Option<bool> {
         Some(self.inner.transparency)
     }
 
+    fn native_visual_info(&self) -> Option<NativeVisual> {
+        None
+    }
+
     fn api(&self) -> Api {
         Api::OPENGL
     }
@@ -1,12 +1,14 @@
 //! Everything related to `NSOpenGLContext`.
 
+use std::ffi::c_void;
 use std::fmt;
 use std::marker::PhantomData;
+use std::ptr::NonNull;
 
 use cgl::CGLSetParameter;
 use objc2::rc::{autoreleasepool, Id};
 use objc2::ClassType;
-use objc2_app_kit::{NSOpenGLCPSwapInterval, NSView};
+use objc2_app_kit::NSView;
 use objc2_foundation::{run_on_main, MainThreadBound};
 
 use crate::config::GetGlConfig;
@@ -15,19 +17,42 @@ use crate::display::GetGlDisplay;
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
-use crate::surface::{SurfaceTypeTrait, SwapInterval};
+use crate::surface::{RawSurface, SurfaceTypeTrait, SwapInterval};
 
 use super::appkit::NSOpenGLContext;
 use super::config::Config;
 use super::display::Display;
 use super::surface::Surface;
 
+#[link(name = "OpenGL", kind = "framework")]
+extern "C" {
+    fn CGLTexImageIOSurface2D(
+        ctx: *mut c_void,
+        target: u32,
+        internal_format: u32,
+        width: i32,
+        height: i32,
+        format: u32,
+        ty: u32,
+        io_surface: *mut c_void,
+        plane: u32,
+    ) -> cgl::CGLError;
+}
+
+#[link(name = "IOSurface", kind = "framework")]
+extern "C" {
+    fn IOSurfaceGetWidthOfPlane(buffer: *mut c_void, plane_index: usize) -> usize;
+    fn IOSurfaceGetHeightOfPlane(buffer: *mut c_void, plane_index: usize) -> usize;
+}
+
 impl Display {
     pub(crate) unsafe fn create_context(
         &self,
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.validate()?;
+
         let share_context = match context_attributes.shared_context.as_ref() {
             Some(RawContext::Cgl(share_context)) => unsafe {
                 share_context.cast::<NSOpenGLContext>().as_ref()
@@ -39,7 +64,9 @@ impl Display {
             return Err(ErrorKind::NotSupported("gles is not supported with CGL").into());
         }
 
-        if context_attributes.robustness != Robustness::NotRobust {
+        if context_attributes.robustness != Robustness::NotRobust
+            || context_attributes.reset_notification.is_some()
+        {
             return Err(ErrorKind::NotSupported("robustness is not supported with CGL").into());
         }
 
@@ -95,10 +122,10 @@ impl NotCurrentGlContext for NotCurrentContext {
         Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<Self::PossiblyCurrentContext> {
         Err(self.inner.make_current_draw_read(surface_draw, surface_read).into())
     }
@@ -143,6 +170,72 @@ pub struct PossiblyCurrentContext {
     _nosendsync: PhantomData<*mut ()>,
 }
 
+impl PossiblyCurrentContext {
+    /// Bind the context to the given virtual screen, making the renderer of
+    /// that screen process the further commands.
+    ///
+    /// The `screen` is the [`VirtualScreen::index`] from the
+    /// [`Config::virtual_screens`] of the context's config.
+    ///
+    /// [`VirtualScreen::index`]: super::config::VirtualScreen::index
+    pub fn set_virtual_screen(&self, screen: i32) -> Result<()> {
+        if !(0..self.inner.config.inner.raw.numberOfVirtualScreens()).contains(&screen) {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        autoreleasepool(|_| unsafe { self.inner.raw.setCurrentVirtualScreen(screen) });
+        Ok(())
+    }
+
+    /// The index of the virtual screen the context is bound to.
+    pub fn virtual_screen(&self) -> i32 {
+        self.inner.raw.currentVirtualScreen()
+    }
+
+    /// Back the texture currently bound to the `target`, which is usually
+    /// `GL_TEXTURE_RECTANGLE`, with the `plane` of the `io_surface` using
+    /// `CGLTexImageIOSurface2D`, without copying it.
+    ///
+    /// The size of the texture is the size of the plane, while the
+    /// `internal_format`, `format` and `ty` are the same as for
+    /// `glTexImage2D`. The context must be current on the calling thread,
+    /// otherwise [`ErrorKind::BadContextState`] is returned.
+    ///
+    /// # Safety
+    ///
+    /// The `io_surface` must be a valid `IOSurfaceRef` and outlive the use of
+    /// the texture.
+    pub unsafe fn bind_iosurface(
+        &self,
+        target: u32,
+        io_surface: NonNull<c_void>,
+        plane: u32,
+        internal_format: u32,
+        format: u32,
+        ty: u32,
+    ) -> Result<()> {
+        if !self.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        unsafe {
+            let width = IOSurfaceGetWidthOfPlane(io_surface.as_ptr(), plane as usize);
+            let height = IOSurfaceGetHeightOfPlane(io_surface.as_ptr(), plane as usize);
+            super::check_error(CGLTexImageIOSurface2D(
+                self.inner.raw.CGLContextObj().cast(),
+                target,
+                internal_format,
+                width as i32,
+                height as i32,
+                format,
+                ty,
+                io_surface.as_ptr(),
+                plane,
+            ))
+        }
+    }
+}
+
 impl PossiblyCurrentGlContext for PossiblyCurrentContext {
     type NotCurrentContext = NotCurrentContext;
     type Surface<T: SurfaceTypeTrait> = Surface<T>;
@@ -160,14 +253,26 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
         }
     }
 
+    fn query_current_surface(&self) -> Option<(RawSurface, RawSurface)> {
+        if !self.is_current() {
+            return None;
+        }
+
+        // Pass the address, since the pointers aren't `Send`.
+        let view =
+            run_on_main(|mtm| self.inner.raw.view(mtm).map(|view| Id::as_ptr(&view) as usize));
+        let view = RawSurface::Cgl(view? as *const _);
+        Some((view, view))
+    }
+
     fn make_current<T: SurfaceTypeTrait>(&self, surface: &Self::Surface<T>) -> Result<()> {
         self.inner.make_current(surface)
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<()> {
         Err(self.inner.make_current_draw_read(surface_draw, surface_read).into())
     }
@@ -210,10 +315,10 @@ pub(crate) struct ContextInner {
 }
 
 impl ContextInner {
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         _surface_draw: &Surface<T>,
-        _surface_read: &Surface<T>,
+        _surface_read: &Surface<U>,
     ) -> ErrorKind {
         ErrorKind::NotSupported("make current draw read isn't supported with CGL")
     }
@@ -236,14 +341,18 @@ impl ContextInner {
         ContextApi::OpenGl(None)
     }
 
-    pub(crate) fn set_swap_interval(&self, interval: SwapInterval) {
-        let interval = match interval {
+    /// Set `kCGLCPSwapInterval` on the underlying `CGLContextObj`.
+    ///
+    /// CGL only supports waiting for a single vertical blank, thus any
+    /// [`SwapInterval::Wait`] is clamped to `1`.
+    pub(crate) fn set_swap_interval(&self, interval: SwapInterval) -> Result<()> {
+        let interval: i32 = match interval {
             SwapInterval::DontWait => 0,
             SwapInterval::Wait(_) => 1,
         };
 
-        autoreleasepool(|_| unsafe {
-            self.raw.setValues_forParameter(&interval, NSOpenGLCPSwapInterval);
+        super::check_error(unsafe {
+            CGLSetParameter(self.raw.CGLContextObj().cast(), cgl::kCGLCPSwapInterval, &interval)
         })
     }
 
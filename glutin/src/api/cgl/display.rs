@@ -1,7 +1,7 @@
 //! A CGL display.
 
 use std::ffi::{self, CStr};
-use std::marker::PhantomData;
+use std::sync::Arc;
 
 use core_foundation::base::TCFType;
 use core_foundation::bundle::{CFBundleGetBundleWithIdentifier, CFBundleGetFunctionPointerForName};
@@ -9,7 +9,9 @@ use core_foundation::string::CFString;
 use raw_window_handle::RawDisplayHandle;
 
 use crate::config::ConfigTemplate;
-use crate::display::{AsRawDisplay, DisplayFeatures, RawDisplay};
+use crate::display::{
+    AsRawDisplay, DisplayFeatures, GpuPreference, ProcLoader, ProcLoaderCell, RawDisplay,
+};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
@@ -22,8 +24,11 @@ use super::surface::Surface;
 /// The CGL display.
 #[derive(Debug, Clone)]
 pub struct Display {
-    // Prevent building of it without constructor.
-    _marker: PhantomData<()>,
+    /// The GPU preference used when choosing the pixel format.
+    pub(crate) gpu_preference: GpuPreference,
+
+    /// The loader overriding the lookup in the OpenGL framework.
+    proc_loader: Arc<ProcLoaderCell>,
 }
 
 impl Display {
@@ -33,11 +38,36 @@ impl Display {
     ///
     /// The function is unsafe for consistency.
     pub unsafe fn new(display: RawDisplayHandle) -> Result<Self> {
+        unsafe { Self::with_gpu_preference(display, GpuPreference::Default) }
+    }
+
+    /// Create CGL display, hinting which GPU should back it.
+    ///
+    /// With [`GpuPreference::HighPerformance`] the offline renderers are not
+    /// allowed, which makes the system switch to the discrete GPU.
+    ///
+    /// # Safety
+    ///
+    /// The function is unsafe for consistency.
+    pub unsafe fn with_gpu_preference(
+        display: RawDisplayHandle,
+        gpu_preference: GpuPreference,
+    ) -> Result<Self> {
         match display {
-            RawDisplayHandle::AppKit(..) => Ok(Display { _marker: PhantomData }),
+            RawDisplayHandle::AppKit(..) => {
+                Ok(Display { gpu_preference, proc_loader: Default::default() })
+            },
             _ => Err(ErrorKind::NotSupported("provided native display is not supported").into()),
         }
     }
+
+    /// Route [`GlDisplay::get_proc_address`] through the given `loader`
+    /// instead of the lookup in the OpenGL framework.
+    ///
+    /// See [`crate::display::Display::set_proc_loader`] for details.
+    pub fn set_proc_loader(&self, loader: ProcLoader) -> Result<()> {
+        self.proc_loader.set(loader)
+    }
 }
 
 impl GlDisplay for Display {
@@ -87,6 +117,10 @@ impl GlDisplay for Display {
     }
 
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void {
+        if let Some(loader) = self.proc_loader.get() {
+            return loader(addr);
+        }
+
         let symbol_name = CFString::new(addr.to_str().unwrap());
         let framework_name = CFString::new("com.apple.opengl");
         unsafe {
@@ -14,8 +14,9 @@ use crate::display::GetGlDisplay;
 use crate::error::{ErrorKind, Result};
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, GlSurface, PbufferSurface, PixmapSurface, RawSurface, SurfaceAttributes,
-    SurfaceTypeTrait, SwapInterval, WindowSurface,
+    AsRawSurface, GlFunctions, GlSurface, PbufferSurface, PixmapSurface, RawSurface,
+    ReadPixelsFormat, Rect, SurfaceAttributes, SurfaceOrigin, SurfaceTypeTrait, SwapInterval,
+    WindowSurface,
 };
 
 use super::config::Config;
@@ -146,13 +147,34 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         self.config.is_single_buffered()
     }
 
+    fn origin(&self) -> SurfaceOrigin {
+        SurfaceOrigin::BottomLeft
+    }
+
     fn swap_buffers(&self, context: &Self::Context) -> Result<()> {
         context.inner.flush_buffer()
     }
 
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()> {
-        context.inner.set_swap_interval(interval);
-        Ok(())
+        context.inner.set_swap_interval(interval)
+    }
+
+    fn flush(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::flush(&self.display, context)
+    }
+
+    fn finish(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::finish(&self.display, context)
+    }
+
+    fn read_pixels(
+        &self,
+        context: &Self::Context,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>> {
+        let single_buffered = self.is_single_buffered();
+        GlFunctions::read_pixels(&self.display, context, single_buffered, rect, format)
     }
 
     fn is_current(&self, context: &Self::Context) -> bool {
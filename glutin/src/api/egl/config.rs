@@ -3,23 +3,24 @@
 
 use std::ops::Deref;
 use std::sync::Arc;
-use std::{fmt, mem};
+use std::{fmt, iter, mem};
 
 use raw_window_handle::RawWindowHandle;
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLConfig, EGLint};
+use glutin_egl_sys::egl::types::{EGLConfig, EGLenum, EGLint};
 
 use crate::config::{
-    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, RawConfig,
+    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, NativeVisual, RawConfig,
 };
+use crate::context::Version;
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 
 #[cfg(x11_platform)]
-use crate::platform::x11::{X11GlConfigExt, X11VisualInfo};
+use crate::platform::x11::{self, X11GlConfigExt, X11VisualInfo};
 
 use super::display::Display;
 
@@ -127,20 +128,7 @@ impl Display {
 
         config_attributes.push(egl::RENDERABLE_TYPE as EGLint);
         let api = if let Some(requested_api) = template.api {
-            let mut api = 0;
-            if requested_api.contains(Api::GLES1) {
-                api |= egl::OPENGL_ES_BIT;
-            }
-            if requested_api.contains(Api::GLES2) {
-                api |= egl::OPENGL_ES2_BIT;
-            }
-            if requested_api.contains(Api::GLES3) {
-                api |= egl::OPENGL_ES3_BIT;
-            }
-            if requested_api.contains(Api::OPENGL) {
-                api |= egl::OPENGL_BIT;
-            }
-            api
+            self.api_bits(requested_api)?
         } else {
             // NOTE: use ES2 by default to avoid matching pure ES1 configs,
             // for more see https://github.com/rust-windowing/glutin/issues/1586.
@@ -148,6 +136,12 @@ impl Display {
         };
         config_attributes.push(api as EGLint);
 
+        // Add conformance.
+        if let Some(conformant) = template.conformant {
+            config_attributes.push(egl::CONFORMANT as EGLint);
+            config_attributes.push(self.api_bits(conformant)? as EGLint);
+        }
+
         // Add maximum height of pbuffer.
         if let Some(pbuffer_width) = template.max_pbuffer_width {
             config_attributes.push(egl::MAX_PBUFFER_WIDTH as EGLint);
@@ -163,6 +157,12 @@ impl Display {
         // Push `egl::NONE` to terminate the list.
         config_attributes.push(egl::NONE as EGLint);
 
+        // Software rasterizers don't mark their configs with `EGL_SLOW_CONFIG`, thus
+        // check the device backing the display.
+        if template.hardware_accelerated == Some(true) && self.is_software() {
+            return Ok(Box::new(iter::empty()));
+        }
+
         let mut configs_number = self.configs_number() as EGLint;
         let mut found_configs: Vec<EGLConfig> =
             unsafe { vec![mem::zeroed(); configs_number as usize] };
@@ -183,6 +183,7 @@ impl Display {
             found_configs.set_len(configs_number as usize);
         }
 
+        let native_visual_id = template.native_window.and_then(|w| self.native_window_visual(w));
         let configs = found_configs
             .into_iter()
             .map(move |raw| {
@@ -195,23 +196,93 @@ impl Display {
                 //
                 // XXX This can't be done by passing visual in the EGL attributes
                 // when calling `eglChooseConfig` since the visual is ignored.
-                match template.native_window {
-                    Some(RawWindowHandle::Xcb(xcb)) => {
-                        xcb.visual_id.map_or(false, |id| id.get() == config.native_visual())
-                    },
-                    Some(RawWindowHandle::Xlib(xlib)) if xlib.visual_id > 0 => {
-                        xlib.visual_id as u32 == config.native_visual()
-                    },
-                    _ => true,
-                }
+                native_visual_id.map_or(true, |id| id == config.native_visual())
             })
             .filter(move |config| {
                 !template.transparency || config.supports_transparency().unwrap_or(true)
-            });
+            })
+            .filter(move |config| template.within_max_sizes(config));
 
         Ok(Box::new(configs))
     }
 
+    /// The visual of the X11 `native_window`, queried from the server when the
+    /// handle doesn't carry it.
+    fn native_window_visual(&self, native_window: RawWindowHandle) -> Option<u32> {
+        match native_window {
+            RawWindowHandle::Xcb(xcb) if xcb.visual_id.is_some() => {
+                xcb.visual_id.map(|id| id.get())
+            },
+            RawWindowHandle::Xlib(xlib) if xlib.visual_id > 0 => Some(xlib.visual_id as u32),
+            #[cfg(x11_platform)]
+            RawWindowHandle::Xlib(_) | RawWindowHandle::Xcb(_) => {
+                let window = match native_window {
+                    RawWindowHandle::Xlib(xlib) => xlib.window,
+                    RawWindowHandle::Xcb(xcb) => xcb.window.get() as _,
+                    _ => unreachable!(),
+                };
+                match *self.inner._native_display? {
+                    raw_window_handle::RawDisplayHandle::Xlib(display_handle) => unsafe {
+                        x11::window_visual_id(display_handle.display?.as_ptr().cast(), window)
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Convert the `api` to the `EGL_RENDERABLE_TYPE` bits.
+    fn api_bits(&self, api: Api) -> Result<EGLenum> {
+        let mut bits = 0;
+        if api.contains(Api::GLES1) {
+            bits |= egl::OPENGL_ES_BIT;
+        }
+        if api.contains(Api::GLES2) {
+            bits |= egl::OPENGL_ES2_BIT;
+        }
+        if api.contains(Api::GLES3) {
+            // The bit is only known since EGL 1.5 or with `EGL_KHR_create_context`,
+            // otherwise `eglChooseConfig` fails with `EGL_BAD_ATTRIBUTE`.
+            if self.inner.version < Version::new(1, 5)
+                && !self.inner.display_extensions.contains("EGL_KHR_create_context")
+            {
+                return Err(ErrorKind::NotSupported(
+                    "EGL_OPENGL_ES3_BIT requires EGL 1.5 or EGL_KHR_create_context",
+                )
+                .into());
+            }
+            bits |= egl::OPENGL_ES3_BIT;
+        }
+        if api.contains(Api::OPENGL) {
+            bits |= egl::OPENGL_BIT;
+        }
+        Ok(bits)
+    }
+
+    /// Find the config with the given `EGL_CONFIG_ID`.
+    pub(crate) fn config_from_id(&self, config_id: EGLint) -> Option<Config> {
+        let attrs = [egl::CONFIG_ID as EGLint, config_id, egl::NONE as EGLint];
+        let mut raw = std::ptr::null();
+        let mut num_configs = 0;
+        unsafe {
+            if self.inner.egl.ChooseConfig(
+                *self.inner.raw,
+                attrs.as_ptr(),
+                &mut raw,
+                1,
+                &mut num_configs,
+            ) == egl::FALSE
+                || num_configs == 0
+            {
+                return None;
+            }
+        }
+
+        let inner = Arc::new(ConfigInner { display: self.clone(), raw: EglConfig(raw) });
+        Some(Config { inner })
+    }
+
     fn configs_number(&self) -> usize {
         unsafe {
             let mut num_configs = 0;
@@ -237,6 +308,105 @@ impl Config {
         unsafe { self.raw_attribute(egl::NATIVE_VISUAL_ID as EGLint) as u32 }
     }
 
+    /// The DRM format modifiers supported for the config's format.
+    ///
+    /// The format is the [`Self::native_visual`] interpreted as a DRM fourcc,
+    /// like it's done on the GBM platform. An empty list is returned when
+    /// `EGL_EXT_image_dma_buf_import_modifiers` is not supported or the format
+    /// is unknown to the implementation.
+    pub fn drm_format_modifiers(&self) -> Vec<u64> {
+        let display = &self.inner.display.inner;
+        if !display.display_extensions.contains("EGL_EXT_image_dma_buf_import_modifiers")
+            || !egl::QueryDmaBufModifiersEXT::is_loaded()
+        {
+            return Vec::new();
+        }
+
+        let format = self.native_visual() as EGLint;
+        unsafe {
+            let mut num_modifiers = 0;
+            if display.egl.QueryDmaBufModifiersEXT(
+                *display.raw,
+                format,
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+                || num_modifiers <= 0
+            {
+                return Vec::new();
+            }
+
+            let mut modifiers = vec![0; num_modifiers as usize];
+            if display.egl.QueryDmaBufModifiersEXT(
+                *display.raw,
+                format,
+                num_modifiers,
+                modifiers.as_mut_ptr(),
+                std::ptr::null_mut(),
+                &mut num_modifiers,
+            ) == egl::FALSE
+            {
+                return Vec::new();
+            }
+
+            modifiers.truncate(num_modifiers.max(0) as usize);
+            modifiers
+        }
+    }
+
+    /// Whether the surfaces created with the config could preserve the back
+    /// buffer across swaps, that is the `EGL_SWAP_BEHAVIOR_PRESERVED_BIT` is
+    /// present in its `EGL_SURFACE_TYPE`.
+    ///
+    /// See [`Surface::set_swap_behavior`].
+    ///
+    /// [`Surface::set_swap_behavior`]: super::surface::Surface::set_swap_behavior
+    pub fn supports_preserved_swap(&self) -> bool {
+        let raw_ty = unsafe { self.raw_attribute(egl::SURFACE_TYPE as EGLint) as u32 };
+        raw_ty & egl::SWAP_BEHAVIOR_PRESERVED_BIT as u32 != 0
+    }
+
+    /// Whether the multisample buffer of the surfaces created with the config
+    /// could be resolved with the box filter, that is the
+    /// `EGL_MULTISAMPLE_RESOLVE_BOX_BIT` is present in its `EGL_SURFACE_TYPE`.
+    ///
+    /// See [`Surface::set_multisample_resolve`].
+    ///
+    /// [`Surface::set_multisample_resolve`]: super::surface::Surface::set_multisample_resolve
+    pub fn supports_box_resolve(&self) -> bool {
+        let raw_ty = unsafe { self.raw_attribute(egl::SURFACE_TYPE as EGLint) as u32 };
+        raw_ty & egl::MULTISAMPLE_RESOLVE_BOX_BIT as u32 != 0
+    }
+
+    /// The client Apis the config is conformant for, read from
+    /// `EGL_CONFORMANT`.
+    ///
+    /// The contexts of the Apis the config is not conformant for could still
+    /// be created, but they may fail to pass the conformance tests.
+    pub fn conformant(&self) -> Api {
+        let raw_api = unsafe { self.raw_attribute(egl::CONFORMANT as EGLint) as u32 };
+        api_from_bits(raw_api)
+    }
+
+    /// The minimum swap interval the surfaces created with the config accept,
+    /// read from `EGL_MIN_SWAP_INTERVAL`.
+    pub fn min_swap_interval(&self) -> u32 {
+        unsafe { self.raw_attribute(egl::MIN_SWAP_INTERVAL as EGLint) as u32 }
+    }
+
+    /// The maximum swap interval the surfaces created with the config accept,
+    /// read from `EGL_MAX_SWAP_INTERVAL`.
+    pub fn max_swap_interval(&self) -> u32 {
+        unsafe { self.raw_attribute(egl::MAX_SWAP_INTERVAL as EGLint) as u32 }
+    }
+
+    /// The `EGL_CONFIG_ID` of the config.
+    pub(crate) fn config_id(&self) -> EGLint {
+        unsafe { self.raw_attribute(egl::CONFIG_ID as EGLint) }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -306,6 +476,10 @@ impl GlConfig for Config {
         unsafe { self.raw_attribute(egl::SAMPLES as EGLint) as u8 }
     }
 
+    fn num_sample_buffers(&self) -> u8 {
+        unsafe { self.raw_attribute(egl::SAMPLE_BUFFERS as EGLint) as u8 }
+    }
+
     fn config_surface_types(&self) -> ConfigSurfaceTypes {
         let mut ty = ConfigSurfaceTypes::empty();
 
@@ -323,8 +497,23 @@ impl GlConfig for Config {
         ty
     }
 
+    fn max_pbuffer_width(&self) -> u32 {
+        unsafe { self.raw_attribute(egl::MAX_PBUFFER_WIDTH as EGLint) as u32 }
+    }
+
+    fn max_pbuffer_height(&self) -> u32 {
+        unsafe { self.raw_attribute(egl::MAX_PBUFFER_HEIGHT as EGLint) as u32 }
+    }
+
+    fn max_pbuffer_pixels(&self) -> u32 {
+        unsafe { self.raw_attribute(egl::MAX_PBUFFER_PIXELS as EGLint) as u32 }
+    }
+
     fn hardware_accelerated(&self) -> bool {
-        unsafe { self.raw_attribute(egl::CONFIG_CAVEAT as EGLint) != egl::SLOW_CONFIG as EGLint }
+        !self.inner.display.is_software()
+            && unsafe {
+                self.raw_attribute(egl::CONFIG_CAVEAT as EGLint) != egl::SLOW_CONFIG as EGLint
+            }
     }
 
     #[cfg(not(any(wayland_platform, x11_platform)))]
@@ -332,6 +521,20 @@ impl GlConfig for Config {
         None
     }
 
+    fn native_visual_info(&self) -> Option<NativeVisual> {
+        match *self.inner.display.inner._native_display? {
+            #[cfg(x11_platform)]
+            raw_window_handle::RawDisplayHandle::Xlib(_) => {
+                self.x11_visual().map(|visual| visual.native_visual())
+            },
+            raw_window_handle::RawDisplayHandle::Gbm(_) => {
+                let format = unsafe { self.raw_attribute(egl::NATIVE_VISUAL_ID as EGLint) };
+                Some(NativeVisual::Drm(format as u32))
+            },
+            _ => None,
+        }
+    }
+
     #[cfg(any(wayland_platform, x11_platform))]
     fn supports_transparency(&self) -> Option<bool> {
         use raw_window_handle::RawDisplayHandle;
@@ -347,23 +550,28 @@ impl GlConfig for Config {
     }
 
     fn api(&self) -> Api {
-        let mut api = Api::empty();
         let raw_api = unsafe { self.raw_attribute(egl::RENDERABLE_TYPE as EGLint) as u32 };
-        if raw_api & egl::OPENGL_BIT as u32 != 0 {
-            api.insert(Api::OPENGL);
-        }
-        if raw_api & egl::OPENGL_ES_BIT as u32 != 0 {
-            api.insert(Api::GLES1);
-        }
-        if raw_api & egl::OPENGL_ES2_BIT as u32 != 0 {
-            api.insert(Api::GLES2);
-        }
-        if raw_api & egl::OPENGL_ES3_BIT as u32 != 0 {
-            api.insert(Api::GLES3);
-        }
+        api_from_bits(raw_api)
+    }
+}
 
-        api
+/// Convert the `EGL_RENDERABLE_TYPE` bits to the [`Api`].
+fn api_from_bits(raw_api: u32) -> Api {
+    let mut api = Api::empty();
+    if raw_api & egl::OPENGL_BIT as u32 != 0 {
+        api.insert(Api::OPENGL);
+    }
+    if raw_api & egl::OPENGL_ES_BIT as u32 != 0 {
+        api.insert(Api::GLES1);
     }
+    if raw_api & egl::OPENGL_ES2_BIT as u32 != 0 {
+        api.insert(Api::GLES2);
+    }
+    if raw_api & egl::OPENGL_ES3_BIT as u32 != 0 {
+        api.insert(Api::GLES3);
+    }
+
+    api
 }
 
 impl GetGlDisplay for Config {
@@ -391,6 +599,16 @@ impl X11GlConfigExt for Config {
             _ => None,
         }
     }
+
+    fn x11_visual_id(&self) -> Option<u32> {
+        match *self.inner.display.inner._native_display? {
+            raw_window_handle::RawDisplayHandle::Xlib(_)
+            | raw_window_handle::RawDisplayHandle::Xcb(_) => {
+                Some(self.native_visual()).filter(|&xid| xid != 0)
+            },
+            _ => None,
+        }
+    }
 }
 
 impl Sealed for Config {}
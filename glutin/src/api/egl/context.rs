@@ -9,17 +9,20 @@ use glutin_egl_sys::{egl, EGLContext};
 
 use crate::config::{Api, GetGlConfig};
 use crate::context::{
-    self, AsRawContext, ContextApi, ContextAttributes, GlProfile, RawContext, Robustness, Version,
+    self, AsRawContext, ContextApi, ContextAttributes, GlProfile, RawContext, ReleaseBehavior,
+    ResetNotification, Robustness, Version,
 };
+use crate::current_guard;
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
-use crate::surface::SurfaceTypeTrait;
+use crate::surface::{RawSurface, SurfaceTypeTrait};
 
 use super::config::Config;
 use super::display::Display;
 use super::surface::Surface;
+use super::Egl;
 
 impl Display {
     pub(crate) unsafe fn create_context(
@@ -27,6 +30,19 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.validate()?;
+
+        let is_one_five = self.inner.version >= Version::new(1, 5);
+        let supports_create_context =
+            is_one_five || self.inner.display_extensions.contains("EGL_KHR_create_context");
+        if supports_create_context {
+            let result = context_attributes
+                .with_highest_core_version(|attrs| unsafe { self.create_context(config, attrs) });
+            if let Some((context, _)) = result {
+                return Ok(context);
+            }
+        }
+
         let mut attrs = Vec::<EGLint>::new();
 
         let supports_opengl = self.inner.version > Version::new(1, 3);
@@ -54,8 +70,7 @@ impl Display {
             },
         };
 
-        let is_one_five = self.inner.version >= Version::new(1, 5);
-        if is_one_five || self.inner.display_extensions.contains("EGL_KHR_create_context") {
+        if supports_create_context {
             let mut flags = 0;
 
             // Add profile for the OpenGL Api.
@@ -91,14 +106,9 @@ impl Display {
                     attrs.push(egl::TRUE as EGLint);
                     requested_no_error = true;
                 },
-                Robustness::RobustLoseContextOnReset if has_robustsess => {
-                    attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
-                    attrs.push(egl::LOSE_CONTEXT_ON_RESET as EGLint);
-                    flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR;
-                },
-                Robustness::RobustNoResetNotification if has_robustsess => {
-                    attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
-                    attrs.push(egl::NO_RESET_NOTIFICATION as EGLint);
+                Robustness::RobustLoseContextOnReset | Robustness::RobustNoResetNotification
+                    if has_robustsess =>
+                {
                     flags |= egl::CONTEXT_OPENGL_ROBUST_ACCESS_BIT_KHR;
                 },
                 _ => {
@@ -108,11 +118,31 @@ impl Display {
                 },
             }
 
+            if let Some(reset_notification) = context_attributes.reset_notification() {
+                if !has_robustsess {
+                    return Err(ErrorKind::NotSupported(
+                        "context reset notification is not supported",
+                    )
+                    .into());
+                }
+
+                let strategy = match reset_notification {
+                    ResetNotification::NoResetNotification => egl::NO_RESET_NOTIFICATION,
+                    ResetNotification::LoseContextOnReset => egl::LOSE_CONTEXT_ON_RESET,
+                };
+                attrs.push(egl::CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY as EGLint);
+                attrs.push(strategy as EGLint);
+            }
+
             if context_attributes.debug && is_one_five && !requested_no_error {
                 attrs.push(egl::CONTEXT_OPENGL_DEBUG as EGLint);
                 attrs.push(egl::TRUE as EGLint);
             }
 
+            if context_attributes.forward_compatible && api == egl::OPENGL_API {
+                flags |= egl::CONTEXT_OPENGL_FORWARD_COMPATIBLE_BIT_KHR;
+            }
+
             if flags != 0 {
                 attrs.push(egl::CONTEXT_FLAGS_KHR as EGLint);
                 attrs.push(flags as EGLint);
@@ -121,11 +151,37 @@ impl Display {
             // EGL 1.3 uses that to indicate client version instead of major/minor. The
             // constant is the same as `CONTEXT_MAJOR_VERSION`.
             if let Some(version) = version {
+                // Don't silently downgrade the requested minor version, since it can't be
+                // passed.
+                if version.minor != 0 {
+                    return Err(ErrorKind::NotSupported(
+                        "requesting minor version requires EGL_KHR_create_context",
+                    )
+                    .into());
+                }
+
                 attrs.push(egl::CONTEXT_CLIENT_VERSION as EGLint);
                 attrs.push(version.major as EGLint);
             }
         }
 
+        // Flush control.
+        if self.inner.features.contains(DisplayFeatures::CONTEXT_RELEASE_BEHAVIOR) {
+            match context_attributes.release_behavior {
+                // This is the default behavior in specification.
+                ReleaseBehavior::Flush => (),
+                ReleaseBehavior::None => {
+                    attrs.push(egl::CONTEXT_RELEASE_BEHAVIOR_KHR as EGLint);
+                    attrs.push(egl::CONTEXT_RELEASE_BEHAVIOR_NONE_KHR as EGLint);
+                },
+            }
+        } else if context_attributes.release_behavior != ReleaseBehavior::Flush {
+            return Err(ErrorKind::NotSupported(
+                "flush control behavior EGL_KHR_context_flush_control",
+            )
+            .into());
+        }
+
         attrs.push(egl::NONE as EGLint);
 
         let shared_context = if let Some(shared_context) =
@@ -158,11 +214,63 @@ impl Display {
                 return Err(super::check_error().err().unwrap());
             }
 
-            let inner =
-                ContextInner { display: self.clone(), config, raw: EglContext(context), api };
+            let inner = ContextInner {
+                display: self.clone(),
+                config,
+                raw: EglContext(context),
+                api,
+                version,
+                owned: true,
+            };
             Ok(NotCurrentContext::new(inner))
         }
     }
+
+    /// Wrap the `EGLContext` created outside of glutin.
+    ///
+    /// The context is not destroyed when the returned value is dropped, the
+    /// caller remains the owner of it.
+    ///
+    /// This function returns [`Err`] if the context wasn't created with the
+    /// `config`.
+    ///
+    /// # Safety
+    ///
+    /// The `raw` must be a valid `EGLContext` created on this display, and it
+    /// must outlive the returned context.
+    pub unsafe fn context_from_raw(
+        &self,
+        config: &Config,
+        raw: EGLContext,
+    ) -> Result<PossiblyCurrentContext> {
+        let query = |attribute: egl::types::EGLenum| {
+            let mut value = 0;
+            if unsafe {
+                self.inner.egl.QueryContext(*self.inner.raw, raw, attribute as EGLint, &mut value)
+            } == egl::FALSE
+            {
+                Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadContext.into()))
+            } else {
+                Ok(value)
+            }
+        };
+
+        if query(egl::CONFIG_ID)? != config.config_id() {
+            return Err(ErrorKind::BadMatch.into());
+        }
+
+        let api = query(egl::CONTEXT_CLIENT_TYPE)? as EGLenum;
+
+        let inner = ContextInner {
+            display: self.clone(),
+            config: config.clone(),
+            raw: EglContext(raw),
+            api,
+            version: None,
+            owned: false,
+        };
+        Ok(PossiblyCurrentContext { inner, _nosendsync: PhantomData })
+    }
 }
 
 /// A wrapper around `EGLContext` that is known to be not current.
@@ -200,10 +308,10 @@ impl NotCurrentGlContext for NotCurrentContext {
         Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         self,
         surface_draw: &Surface<T>,
-        surface_read: &Surface<T>,
+        surface_read: &Surface<U>,
     ) -> Result<PossiblyCurrentContext> {
         self.inner.make_current_draw_read(surface_draw, surface_read)?;
         Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
@@ -264,20 +372,33 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
     }
 
     fn is_current(&self) -> bool {
-        unsafe {
-            self.inner.bind_api();
-            self.inner.display.inner.egl.GetCurrentContext() == *self.inner.raw
-        }
+        self.inner
+            .with_own_api(|egl| unsafe { egl.GetCurrentContext() == *self.inner.raw })
+            .unwrap_or(false)
+    }
+
+    fn query_current_surface(&self) -> Option<(RawSurface, RawSurface)> {
+        self.inner
+            .with_own_api(|egl| unsafe {
+                if egl.GetCurrentContext() != *self.inner.raw {
+                    return None;
+                }
+
+                let draw = egl.GetCurrentSurface(egl::DRAW as EGLint);
+                let read = egl.GetCurrentSurface(egl::READ as EGLint);
+                Some((RawSurface::Egl(draw), RawSurface::Egl(read)))
+            })
+            .flatten()
     }
 
     fn make_current<T: SurfaceTypeTrait>(&self, surface: &Self::Surface<T>) -> Result<()> {
         self.inner.make_current_draw_read(surface, surface)
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<()> {
         self.inner.make_current_draw_read(surface_draw, surface_read)
     }
@@ -318,10 +439,15 @@ pub(crate) struct ContextInner {
     config: Config,
     raw: EglContext,
     api: egl::types::EGLenum,
+    /// The version requested when creating the context, if known.
+    version: Option<Version>,
+    /// Whether the context was created by glutin and should be destroyed.
+    owned: bool,
 }
 
 impl ContextInner {
     fn make_current_surfaceless(&self) -> Result<()> {
+        current_guard::check_make_current(*self.raw as usize);
         unsafe {
             if self.display.inner.egl.MakeCurrent(
                 *self.display.inner.raw,
@@ -332,16 +458,18 @@ impl ContextInner {
             {
                 super::check_error()
             } else {
+                current_guard::mark_current(*self.raw as usize, self.api);
                 Ok(())
             }
         }
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Surface<T>,
-        surface_read: &Surface<T>,
+        surface_read: &Surface<U>,
     ) -> Result<()> {
+        current_guard::check_make_current(*self.raw as usize);
         unsafe {
             let draw = surface_draw.raw;
             let read = surface_read.raw;
@@ -350,6 +478,7 @@ impl ContextInner {
             {
                 super::check_error()
             } else {
+                current_guard::mark_current(*self.raw as usize, self.api);
                 Ok(())
             }
         }
@@ -368,16 +497,39 @@ impl ContextInner {
             {
                 super::check_error()
             } else {
+                current_guard::mark_not_current(self.api);
                 Ok(())
             }
         }
     }
 
+    /// Run `f` with the Api of the context bound, restoring the Api bound on
+    /// the calling thread afterwards, since the current context and surfaces
+    /// are tracked per Api.
+    ///
+    /// [`None`] is returned when the Api couldn't be bound.
+    fn with_own_api<R>(&self, f: impl FnOnce(&Egl) -> R) -> Option<R> {
+        unsafe {
+            let egl = self.display.inner.egl;
+            let bound_api = egl.QueryAPI();
+            if bound_api == self.api {
+                return Some(f(egl));
+            }
+
+            if egl.BindAPI(self.api) == egl::FALSE {
+                return None;
+            }
+            let result = f(egl);
+            egl.BindAPI(bound_api);
+            Some(result)
+        }
+    }
+
     fn context_api(&self) -> ContextApi {
         match self.query_attribute(egl::CONTEXT_CLIENT_TYPE as EGLint).map(|a| a as EGLenum) {
-            Some(egl::OPENGL_API) => ContextApi::OpenGl(None),
+            Some(egl::OPENGL_API) => ContextApi::OpenGl(self.version),
             // Map the rest to the GLES.
-            _ => ContextApi::Gles(None),
+            _ => ContextApi::Gles(self.version),
         }
     }
 
@@ -420,9 +572,15 @@ impl ContextInner {
 
 impl Drop for ContextInner {
     fn drop(&mut self) {
-        unsafe {
-            self.display.inner.egl.DestroyContext(*self.display.inner.raw, *self.raw);
+        if self.owned {
+            unsafe {
+                self.display.inner.egl.DestroyContext(*self.display.inner.raw, *self.raw);
+            }
         }
+
+        // The adopted context could be destroyed by its owner right after, and the
+        // handle reused by the new one.
+        current_guard::forget(*self.raw as usize);
     }
 }
 
@@ -432,6 +590,7 @@ impl fmt::Debug for ContextInner {
             .field("display", &self.display.inner.raw)
             .field("config", &self.config.inner.raw)
             .field("raw", &self.raw)
+            .field("owned", &self.owned)
             .finish()
     }
 }
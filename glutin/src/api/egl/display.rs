@@ -6,6 +6,8 @@ use std::fmt;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::os::raw::c_char;
+use std::path::Path;
+use std::ptr::NonNull;
 use std::sync::Arc;
 
 use glutin_egl_sys::egl;
@@ -13,11 +15,15 @@ use glutin_egl_sys::egl::types::{EGLAttrib, EGLDisplay, EGLint};
 
 use once_cell::sync::OnceCell;
 
-use raw_window_handle::{RawDisplayHandle, XlibDisplayHandle};
+use raw_window_handle::{GbmDisplayHandle, RawDisplayHandle, XlibDisplayHandle};
 
 use crate::config::ConfigTemplate;
 use crate::context::Version;
-use crate::display::{AsRawDisplay, DisplayFeatures, GetDisplayExtensions, RawDisplay};
+use crate::current_guard;
+use crate::display::{
+    AngleBackend, AsRawDisplay, DisplayFeatures, DriverQuirks, GetDisplayExtensions, GpuPreference,
+    ProcLoader, ProcLoaderCell, RawDisplay,
+};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
@@ -50,6 +56,43 @@ impl Display {
     /// `EGL_DEFAULT_DISPLAY`, which is not recommended or will
     /// work on a platform with a concept of native display, like Wayland.
     pub unsafe fn new(raw_display: RawDisplayHandle) -> Result<Self> {
+        unsafe { Self::with_gpu_preference(raw_display, GpuPreference::Default) }
+    }
+
+    /// Create EGL display with the native display, hinting which GPU should
+    /// back it.
+    ///
+    /// The hint is only honored by ANGLE with
+    /// `EGL_ANGLE_display_power_preference`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn with_gpu_preference(
+        raw_display: RawDisplayHandle,
+        gpu_preference: GpuPreference,
+    ) -> Result<Self> {
+        unsafe { Self::get_uninitialized(raw_display, gpu_preference)?.initialize() }
+    }
+
+    /// Create EGL display with the native display like [`Display::new`], but
+    /// without calling `eglInitialize`, which could be slow, so the cost
+    /// could be paid later off the critical path.
+    ///
+    /// The display must be initialized with
+    /// [`UninitializedDisplay::initialize`] before any other operation.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn new_uninitialized(raw_display: RawDisplayHandle) -> Result<UninitializedDisplay> {
+        unsafe { Self::get_uninitialized(raw_display, GpuPreference::Default) }
+    }
+
+    unsafe fn get_uninitialized(
+        raw_display: RawDisplayHandle,
+        gpu_preference: GpuPreference,
+    ) -> Result<UninitializedDisplay> {
         let egl = match EGL.as_ref() {
             Some(egl) => egl,
             None => return Err(ErrorKind::NotFound.into()),
@@ -64,7 +107,7 @@ impl Display {
                 if err.error_kind() == ErrorKind::BadAttribute {
                     Err(err)
                 } else {
-                    Self::get_platform_display_ext(egl, raw_display)
+                    Self::get_platform_display_ext(egl, raw_display, gpu_preference, None)
                 }
             })
             .or_else(|err| {
@@ -75,6 +118,68 @@ impl Display {
                 }
             })?;
 
+        Ok(UninitializedDisplay { egl, raw: display, raw_display_handle: raw_display })
+    }
+
+    /// Create EGL display with ANGLE, forcing the given backend of it.
+    ///
+    /// Unlike [`Display::new()`], this function doesn't fallback to the other
+    /// platforms and returns [`Err`] when `EGL_ANGLE_platform_angle` is not
+    /// supported. The `gpu_preference` is honored like in
+    /// [`Display::with_gpu_preference`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn with_angle_backend(
+        raw_display: RawDisplayHandle,
+        angle_backend: AngleBackend,
+        gpu_preference: GpuPreference,
+    ) -> Result<Self> {
+        let egl = match EGL.as_ref() {
+            Some(egl) => egl,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        let display =
+            Self::get_platform_display_ext(egl, raw_display, gpu_preference, Some(angle_backend))?;
+
+        Self::initialize_display(egl, display, Some(raw_display))
+    }
+
+    /// Create EGL display for the GBM device, which is the way to render
+    /// directly into the DRM planes without a compositor.
+    ///
+    /// Unlike [`Display::new()`] with the [`RawDisplayHandle::Gbm`], this
+    /// function doesn't fallback to `eglGetDisplay` and returns [`Err`] when
+    /// neither `EGL_KHR_platform_gbm` nor `EGL_MESA_platform_gbm` is supported.
+    ///
+    /// # Safety
+    ///
+    /// `gbm_device` must point to a valid `gbm_device` which outlives the
+    /// display.
+    pub unsafe fn with_gbm_device(gbm_device: NonNull<ffi::c_void>) -> Result<Self> {
+        let egl = match EGL.as_ref() {
+            Some(egl) => egl,
+            None => return Err(ErrorKind::NotFound.into()),
+        };
+
+        let extensions = CLIENT_EXTENSIONS.get_or_init(|| get_extensions(egl, egl::NO_DISPLAY));
+
+        let raw_display = RawDisplayHandle::Gbm(GbmDisplayHandle::new(gbm_device));
+        let display = if extensions.contains("EGL_KHR_platform_gbm") {
+            Self::get_platform_display(egl, raw_display)?
+        } else if extensions.contains("EGL_MESA_platform_gbm") {
+            Self::get_platform_display_ext(egl, raw_display, GpuPreference::Default, None)?
+        } else {
+            return Err(ErrorKind::NotSupported(
+                "neither EGL_KHR_platform_gbm nor EGL_MESA_platform_gbm are supported",
+            )
+            .into());
+        };
+
         Self::initialize_display(egl, display, Some(raw_display))
     }
 
@@ -180,11 +285,27 @@ impl Display {
         Self::initialize_display(egl, platform_display, None)
     }
 
+    /// The path of the DRM render node backing the display, like
+    /// `/dev/dri/renderD128`.
+    ///
+    /// The processes cooperating through the shared `EGLImage`s and native
+    /// fences should open their displays on the same render node.
+    ///
+    /// [`None`] is returned when the [`Device`] of the display can't be
+    /// queried or it doesn't report the render node.
+    pub fn render_node_path(&self) -> Option<&'static Path> {
+        self.device().ok()?.drm_render_device_node_path()
+    }
+
     /// Get the [`Device`] the display is using.
     ///
     /// This function returns [`Err`] if the `EGL_EXT_device_query` or
     /// `EGL_EXT_device_base` extensions are not available.
     pub fn device(&self) -> Result<Device> {
+        Self::query_device(self.inner.egl, &self.inner.raw)
+    }
+
+    fn query_device(egl: &'static Egl, display: &EglDisplay) -> Result<Device> {
         let no_display_extensions = CLIENT_EXTENSIONS.get().unwrap();
 
         // Querying the device of a display only requires EGL_EXT_device_query, but we
@@ -201,11 +322,7 @@ impl Display {
 
         let mut device = MaybeUninit::uninit();
         if unsafe {
-            self.inner.egl.QueryDisplayAttribEXT(
-                *self.inner.raw,
-                egl::DEVICE_EXT as EGLint,
-                device.as_mut_ptr(),
-            )
+            egl.QueryDisplayAttribEXT(**display, egl::DEVICE_EXT as EGLint, device.as_mut_ptr())
         } == egl::FALSE
         {
             // Check for EGL_NOT_INITIALIZED in case the display was externally terminated.
@@ -224,7 +341,20 @@ impl Display {
             "eglQueryDisplayAttribEXT(EGL_DEVICE_EXT) should never return EGL_NO_DEVICE_EXT on \
              success"
         );
-        Device::from_ptr(self.inner.egl, device)
+        Device::from_ptr(egl, device)
+    }
+
+    /// Whether the display is backed by a software device, like llvmpipe.
+    pub(crate) fn is_software(&self) -> bool {
+        self.inner.is_software
+    }
+
+    /// Route [`GlDisplay::get_proc_address`] through the given `loader`
+    /// instead of `eglGetProcAddress`.
+    ///
+    /// See [`crate::display::Display::set_proc_loader`] for details.
+    pub fn set_proc_loader(&self, loader: ProcLoader) -> Result<()> {
+        self.inner.proc_loader.set(loader)
     }
 
     /// Get a reference to the initialized EGL API.
@@ -232,6 +362,64 @@ impl Display {
         self.inner.egl
     }
 
+    /// The EGL version reported by `eglInitialize`.
+    pub fn version(&self) -> Version {
+        self.inner.version
+    }
+
+    /// The vendor of the EGL implementation reported by `eglQueryString`,
+    /// which doesn't require a current context.
+    pub fn vendor(&self) -> Option<String> {
+        self.query_string(egl::VENDOR as EGLint)
+    }
+
+    /// The known issues of the driver backing the display.
+    ///
+    /// See [`crate::display::Display::driver_quirks`] for details.
+    pub fn driver_quirks(&self) -> DriverQuirks {
+        let mut quirks = DriverQuirks::empty();
+
+        let vendor = self.vendor().unwrap_or_default();
+        let is_wayland =
+            matches!(self.inner._native_display.as_deref(), Some(RawDisplayHandle::Wayland(_)));
+        quirks.set(
+            DriverQuirks::NVIDIA_WAYLAND_TERMINATE_ON_EXIT,
+            is_wayland && vendor.contains("NVIDIA"),
+        );
+
+        let device_name = self.device().ok().and_then(|device| device.name()).unwrap_or_default();
+        quirks.set(
+            DriverQuirks::MESA_ZINK_SOFTWARE,
+            device_name.contains("zink")
+                && (device_name.contains("llvmpipe") || device_name.contains("lavapipe")),
+        );
+
+        let extensions = &self.inner.display_extensions;
+        quirks.set(
+            DriverQuirks::ANGLE_D3D,
+            extensions.contains("EGL_ANGLE_d3d_share_handle_client_buffer")
+                || extensions.contains("EGL_ANGLE_surface_d3d_texture_2d_share_handle"),
+        );
+
+        quirks
+    }
+
+    /// Query the string with the given `name`, like `EGL_CLIENT_APIS`, with
+    /// `eglQueryString`.
+    ///
+    /// [`None`] is returned when the `name` is not known to the
+    /// implementation.
+    pub fn query_string(&self, name: EGLint) -> Option<String> {
+        unsafe {
+            let value = self.inner.egl.QueryString(*self.inner.raw, name);
+            if value.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(value).to_string_lossy().into_owned())
+            }
+        }
+    }
+
     /// Terminate the EGL display.
     ///
     /// When the display is managed by glutin with the
@@ -251,6 +439,46 @@ impl Display {
         }
     }
 
+    /// Release the EGL state of the calling thread with `eglReleaseThread`.
+    ///
+    /// This should be called before exiting each thread which made a context
+    /// current, otherwise the per-thread state of the EGL implementation may
+    /// leak. The context current on the thread is released as well, so it must
+    /// not be used afterwards without making it current again.
+    pub fn release_thread(&self) -> Result<()> {
+        if unsafe { self.inner.egl.ReleaseThread() } == egl::FALSE {
+            return super::check_error();
+        }
+
+        // The contexts current for every client Api are released.
+        for api in [egl::OPENGL_API, egl::OPENGL_ES_API] {
+            current_guard::mark_not_current(api);
+        }
+
+        Ok(())
+    }
+
+    /// Terminate the EGL display like [`Self::terminate`], but only when no
+    /// other objects created by glutin with this display, like configs,
+    /// contexts and surfaces, or its clones are alive.
+    ///
+    /// [`ErrorKind::BadAccess`] is returned otherwise, and the display is left
+    /// intact.
+    ///
+    /// # Safety
+    ///
+    /// The EGL display must not be used by other libraries or by the displays
+    /// created separately for the same native display, since they share the
+    /// same `EGLDisplay`.
+    pub unsafe fn try_terminate(self) -> Result<()> {
+        if Arc::strong_count(&self.inner) > 1 {
+            return Err(ErrorKind::BadAccess.into());
+        }
+
+        unsafe { self.terminate() };
+        Ok(())
+    }
+
     fn get_platform_display(egl: &Egl, display: RawDisplayHandle) -> Result<EglDisplay> {
         if !egl.GetPlatformDisplay.is_loaded() {
             return Err(ErrorKind::NotSupported("eglGetPlatformDisplay is not supported").into());
@@ -328,7 +556,12 @@ impl Display {
         platform_display.map(EglDisplay::Khr)
     }
 
-    fn get_platform_display_ext(egl: &Egl, display: RawDisplayHandle) -> Result<EglDisplay> {
+    fn get_platform_display_ext(
+        egl: &Egl,
+        display: RawDisplayHandle,
+        gpu_preference: GpuPreference,
+        angle_backend: Option<AngleBackend>,
+    ) -> Result<EglDisplay> {
         if !egl.GetPlatformDisplayEXT.is_loaded() {
             return Err(ErrorKind::NotSupported("eglGetPlatformDisplayEXT is not supported").into());
         }
@@ -338,6 +571,46 @@ impl Display {
         let mut attrs = Vec::<EGLint>::with_capacity(5);
         let mut legacy = false;
         let (platform, display) = match display {
+            // The requested ANGLE backend takes precedence over the native platform.
+            _ if angle_backend.is_some() && !extensions.contains("EGL_ANGLE_platform_angle") => {
+                return Err(
+                    ErrorKind::NotSupported("EGL_ANGLE_platform_angle is not supported").into()
+                );
+            },
+            _ if angle_backend.is_some()
+                || matches!(display, RawDisplayHandle::Windows(..))
+                    && extensions.contains("EGL_ANGLE_platform_angle") =>
+            {
+                // Only CreateWindowSurface appears to work with Angle.
+                legacy = true;
+
+                if let Some(angle_backend) = angle_backend {
+                    let platform_type = match angle_backend {
+                        AngleBackend::Default => egl::PLATFORM_ANGLE_TYPE_DEFAULT_ANGLE,
+                        AngleBackend::D3D9 => egl::PLATFORM_ANGLE_TYPE_D3D9_ANGLE,
+                        AngleBackend::D3D11 => egl::PLATFORM_ANGLE_TYPE_D3D11_ANGLE,
+                        AngleBackend::OpenGl => egl::PLATFORM_ANGLE_TYPE_OPENGL_ANGLE,
+                        AngleBackend::Vulkan => egl::PLATFORM_ANGLE_TYPE_VULKAN_ANGLE,
+                        AngleBackend::Metal => egl::PLATFORM_ANGLE_TYPE_METAL_ANGLE,
+                    };
+                    attrs.push(egl::PLATFORM_ANGLE_TYPE_ANGLE as EGLint);
+                    attrs.push(platform_type as EGLint);
+                }
+
+                let power_preference = match gpu_preference {
+                    GpuPreference::LowPower => Some(egl::LOW_POWER_ANGLE),
+                    GpuPreference::HighPerformance => Some(egl::HIGH_POWER_ANGLE),
+                    GpuPreference::Default => None,
+                };
+                if let Some(power_preference) = power_preference
+                    .filter(|_| extensions.contains("EGL_ANGLE_display_power_preference"))
+                {
+                    attrs.push(egl::POWER_PREFERENCE_ANGLE as EGLint);
+                    attrs.push(power_preference as EGLint);
+                }
+
+                (egl::PLATFORM_ANGLE_ANGLE, egl::DEFAULT_DISPLAY as *mut _)
+            },
             RawDisplayHandle::Wayland(handle)
                 if extensions.contains("EGL_EXT_platform_wayland") =>
             {
@@ -371,11 +644,6 @@ impl Display {
                 )
                 .into())
             },
-            RawDisplayHandle::Windows(..) if extensions.contains("EGL_ANGLE_platform_angle") => {
-                // Only CreateWindowSurface appears to work with Angle.
-                legacy = true;
-                (egl::PLATFORM_ANGLE_ANGLE, egl::DEFAULT_DISPLAY as *mut _)
-            },
             _ => {
                 return Err(
                     ErrorKind::NotSupported("provided display handle is not supported").into()
@@ -481,6 +749,11 @@ impl Display {
             extensions.contains("EGL_KHR_create_context_no_error"),
         );
 
+        supported_features.set(
+            DisplayFeatures::CONTEXT_RELEASE_BEHAVIOR,
+            extensions.contains("EGL_KHR_context_flush_control"),
+        );
+
         supported_features
     }
 
@@ -533,6 +806,8 @@ impl Display {
         // Load extensions.
         let display_extensions = get_extensions(egl, *display);
         let features = Self::extract_display_features(&display_extensions, version);
+        let is_software = Self::query_device(egl, &display)
+            .is_ok_and(|device| device.extensions().contains("EGL_MESA_device_software"));
 
         let inner = Arc::new(DisplayInner {
             egl,
@@ -541,6 +816,8 @@ impl Display {
             version,
             display_extensions,
             features,
+            is_software,
+            proc_loader: ProcLoaderCell::default(),
         });
         Ok(Self { inner })
     }
@@ -593,6 +870,10 @@ impl GlDisplay for Display {
     }
 
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void {
+        if let Some(loader) = self.inner.proc_loader.get() {
+            return loader(addr);
+        }
+
         unsafe { self.inner.egl.GetProcAddress(addr.as_ptr()) as *const _ }
     }
 
@@ -635,8 +916,14 @@ pub(crate) struct DisplayInner {
     /// The features supported by the display.
     pub(crate) features: DisplayFeatures,
 
+    /// Whether the display is backed by a software device.
+    pub(crate) is_software: bool,
+
     /// The raw display used to create EGL display.
     pub(crate) _native_display: Option<NativeDisplay>,
+
+    /// The loader overriding `eglGetProcAddress`.
+    pub(crate) proc_loader: ProcLoaderCell,
 }
 
 impl DisplayInner {
@@ -748,6 +1035,34 @@ impl Deref for NativeDisplay {
     }
 }
 
+/// The EGL display which is not initialized yet, see
+/// [`Display::new_uninitialized`].
+///
+/// The display could be only initialized, since all the other operations
+/// require the initialized display.
+pub struct UninitializedDisplay {
+    egl: &'static Egl,
+    raw: EglDisplay,
+    raw_display_handle: RawDisplayHandle,
+}
+
+// The EGL display could be shared between threads.
+unsafe impl Send for UninitializedDisplay {}
+unsafe impl Sync for UninitializedDisplay {}
+
+impl UninitializedDisplay {
+    /// Initialize the display with `eglInitialize`.
+    pub fn initialize(self) -> Result<Display> {
+        Display::initialize_display(self.egl, self.raw, Some(self.raw_display_handle))
+    }
+}
+
+impl fmt::Debug for UninitializedDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UninitializedDisplay").field("raw", &self.raw).finish()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum EglDisplay {
     /// The display was created with the KHR extension.
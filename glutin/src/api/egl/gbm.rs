@@ -0,0 +1,121 @@
+//! Mapping of the GBM buffers for the CPU access.
+//!
+//! The `libgbm` is loaded at runtime, since only a handful of its functions
+//! are needed and the applications already provide the `gbm_surface` created
+//! with it.
+
+use std::ffi::c_void;
+use std::{ptr, slice};
+
+use libloading::Library;
+use once_cell::sync::Lazy;
+
+use crate::surface::MappedBuffer;
+
+/// The `GBM_BO_TRANSFER_READ` flag of `gbm_bo_map`.
+const GBM_BO_TRANSFER_READ: u32 = 1 << 0;
+
+type LockFrontBuffer = unsafe extern "C" fn(*mut c_void) -> *mut c_void;
+type ReleaseBuffer = unsafe extern "C" fn(*mut c_void, *mut c_void);
+type BoMap = unsafe extern "C" fn(
+    *mut c_void,
+    u32,
+    u32,
+    u32,
+    u32,
+    u32,
+    *mut u32,
+    *mut *mut c_void,
+) -> *mut c_void;
+type BoUnmap = unsafe extern "C" fn(*mut c_void, *mut c_void);
+type BoGetU32 = unsafe extern "C" fn(*mut c_void) -> u32;
+
+static GBM: Lazy<Option<Gbm>> = Lazy::new(|| unsafe { Gbm::load() });
+
+struct Gbm {
+    surface_lock_front_buffer: LockFrontBuffer,
+    surface_release_buffer: ReleaseBuffer,
+    bo_map: BoMap,
+    bo_unmap: BoUnmap,
+    bo_get_width: BoGetU32,
+    bo_get_height: BoGetU32,
+    bo_get_format: BoGetU32,
+    _lib: Library,
+}
+
+impl Gbm {
+    unsafe fn load() -> Option<Self> {
+        unsafe {
+            let lib =
+                ["libgbm.so.1", "libgbm.so"].iter().find_map(|path| Library::new(path).ok())?;
+            // Copy the function pointers out before moving the library.
+            let surface_lock_front_buffer = *lib.get(b"gbm_surface_lock_front_buffer\0").ok()?;
+            let surface_release_buffer = *lib.get(b"gbm_surface_release_buffer\0").ok()?;
+            let bo_map = *lib.get(b"gbm_bo_map\0").ok()?;
+            let bo_unmap = *lib.get(b"gbm_bo_unmap\0").ok()?;
+            let bo_get_width = *lib.get(b"gbm_bo_get_width\0").ok()?;
+            let bo_get_height = *lib.get(b"gbm_bo_get_height\0").ok()?;
+            let bo_get_format = *lib.get(b"gbm_bo_get_format\0").ok()?;
+            Some(Self {
+                surface_lock_front_buffer,
+                surface_release_buffer,
+                bo_map,
+                bo_unmap,
+                bo_get_width,
+                bo_get_height,
+                bo_get_format,
+                _lib: lib,
+            })
+        }
+    }
+}
+
+/// Unmap and release the locked front buffer when the mapping is dropped.
+struct FrontBufferGuard {
+    gbm_surface: *mut c_void,
+    bo: *mut c_void,
+    map_data: *mut c_void,
+}
+
+impl Drop for FrontBufferGuard {
+    fn drop(&mut self) {
+        let gbm = GBM.as_ref().unwrap();
+        unsafe {
+            (gbm.bo_unmap)(self.bo, self.map_data);
+            (gbm.surface_release_buffer)(self.gbm_surface, self.bo);
+        }
+    }
+}
+
+/// Lock the front buffer of the `gbm_surface` and map it for reading.
+///
+/// # Safety
+///
+/// The `gbm_surface` must be a valid pointer which outlives the returned
+/// buffer.
+pub(crate) unsafe fn map_front_buffer<'a>(gbm_surface: *mut c_void) -> Option<MappedBuffer<'a>> {
+    let gbm = GBM.as_ref()?;
+    unsafe {
+        let bo = (gbm.surface_lock_front_buffer)(gbm_surface);
+        if bo.is_null() {
+            return None;
+        }
+
+        let width = (gbm.bo_get_width)(bo);
+        let height = (gbm.bo_get_height)(bo);
+        let format = (gbm.bo_get_format)(bo);
+
+        let mut stride = 0;
+        let mut map_data = ptr::null_mut();
+        let data =
+            (gbm.bo_map)(bo, 0, 0, width, height, GBM_BO_TRANSFER_READ, &mut stride, &mut map_data);
+        if data.is_null() {
+            (gbm.surface_release_buffer)(gbm_surface, bo);
+            return None;
+        }
+
+        let guard = FrontBufferGuard { gbm_surface, bo, map_data };
+        let data = slice::from_raw_parts(data as *const u8, stride as usize * height as usize);
+        Some(MappedBuffer::new(data, width, height, stride, format, Box::new(guard)))
+    }
+}
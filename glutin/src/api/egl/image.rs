@@ -0,0 +1,421 @@
+//! Everything related to `EGLImage`.
+
+use std::ffi::{self, CStr};
+use std::fmt;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLImageKHR, EGLenum, EGLint};
+
+use crate::context::{AsRawContext, RawContext};
+use crate::display::GetGlDisplay;
+use crate::error::{ErrorKind, Result};
+use crate::prelude::*;
+use crate::private::Sealed;
+
+use super::context::PossiblyCurrentContext;
+use super::display::Display;
+
+/// The `GL_TEXTURE_EXTERNAL_OES` texture target.
+const TEXTURE_EXTERNAL_OES: u32 = 0x8D65;
+
+/// The maximum number of planes a DMA-BUF could have.
+#[cfg(unix)]
+const MAX_DMABUF_PLANES: usize = 4;
+
+#[cfg(unix)]
+static DMABUF_PLANE_ATTRIBUTES: [[EGLenum; 5]; MAX_DMABUF_PLANES] = [
+    [
+        egl::DMA_BUF_PLANE0_FD_EXT,
+        egl::DMA_BUF_PLANE0_OFFSET_EXT,
+        egl::DMA_BUF_PLANE0_PITCH_EXT,
+        egl::DMA_BUF_PLANE0_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE0_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE1_FD_EXT,
+        egl::DMA_BUF_PLANE1_OFFSET_EXT,
+        egl::DMA_BUF_PLANE1_PITCH_EXT,
+        egl::DMA_BUF_PLANE1_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE1_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE2_FD_EXT,
+        egl::DMA_BUF_PLANE2_OFFSET_EXT,
+        egl::DMA_BUF_PLANE2_PITCH_EXT,
+        egl::DMA_BUF_PLANE2_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE2_MODIFIER_HI_EXT,
+    ],
+    [
+        egl::DMA_BUF_PLANE3_FD_EXT,
+        egl::DMA_BUF_PLANE3_OFFSET_EXT,
+        egl::DMA_BUF_PLANE3_PITCH_EXT,
+        egl::DMA_BUF_PLANE3_MODIFIER_LO_EXT,
+        egl::DMA_BUF_PLANE3_MODIFIER_HI_EXT,
+    ],
+];
+
+/// A single plane of the DMA-BUF.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy)]
+pub struct DmabufPlane<'a> {
+    /// The DMA-BUF file descriptor holding the plane.
+    pub fd: BorrowedFd<'a>,
+    /// The offset of the plane in bytes from the start of the `fd`.
+    pub offset: u32,
+    /// The stride of the plane in bytes.
+    pub stride: u32,
+}
+
+/// The description of the DMA-BUF to import with
+/// [`Display::create_image_from_dmabuf`].
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub struct DmabufDescriptor<'a> {
+    /// The width of the buffer in pixels.
+    pub width: u32,
+    /// The height of the buffer in pixels.
+    pub height: u32,
+    /// The `DRM_FORMAT_*` fourcc code of the buffer.
+    pub fourcc: u32,
+    /// The planes of the buffer, at most 4.
+    pub planes: Vec<DmabufPlane<'a>>,
+    /// The DRM format modifier applied to all the planes.
+    ///
+    /// Requires `EGL_EXT_image_dma_buf_import_modifiers`.
+    pub modifier: Option<u64>,
+}
+
+#[cfg(unix)]
+impl Display {
+    /// Import the DMA-BUF as an [`EglImage`].
+    ///
+    /// The file descriptors are not consumed and could be closed once this
+    /// function returns.
+    ///
+    /// This function returns [`Err`] when `EGL_EXT_image_dma_buf_import`
+    /// isn't supported or when the modifiers or a fourth plane are requested
+    /// without `EGL_EXT_image_dma_buf_import_modifiers`.
+    pub fn create_image_from_dmabuf(&self, descriptor: &DmabufDescriptor<'_>) -> Result<EglImage> {
+        let extensions = &self.inner.display_extensions;
+        if !extensions.contains("EGL_EXT_image_dma_buf_import")
+            || !self.inner.egl.CreateImageKHR.is_loaded()
+        {
+            return Err(
+                ErrorKind::NotSupported("EGL_EXT_image_dma_buf_import is not supported").into()
+            );
+        }
+
+        if descriptor.planes.is_empty() || descriptor.planes.len() > MAX_DMABUF_PLANES {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        let supports_modifiers = extensions.contains("EGL_EXT_image_dma_buf_import_modifiers");
+        if !supports_modifiers
+            && (descriptor.modifier.is_some() || descriptor.planes.len() == MAX_DMABUF_PLANES)
+        {
+            return Err(ErrorKind::NotSupported(
+                "EGL_EXT_image_dma_buf_import_modifiers is not supported",
+            )
+            .into());
+        }
+
+        let mut attrs = Vec::<EGLint>::with_capacity(8 + 10 * descriptor.planes.len());
+
+        attrs.push(egl::WIDTH as EGLint);
+        attrs.push(descriptor.width as EGLint);
+        attrs.push(egl::HEIGHT as EGLint);
+        attrs.push(descriptor.height as EGLint);
+        attrs.push(egl::LINUX_DRM_FOURCC_EXT as EGLint);
+        attrs.push(descriptor.fourcc as EGLint);
+
+        for (plane, [fd, offset, pitch, modifier_lo, modifier_hi]) in
+            descriptor.planes.iter().zip(DMABUF_PLANE_ATTRIBUTES.iter())
+        {
+            attrs.push(*fd as EGLint);
+            attrs.push(plane.fd.as_raw_fd() as EGLint);
+            attrs.push(*offset as EGLint);
+            attrs.push(plane.offset as EGLint);
+            attrs.push(*pitch as EGLint);
+            attrs.push(plane.stride as EGLint);
+
+            if let Some(modifier) = descriptor.modifier {
+                attrs.push(*modifier_lo as EGLint);
+                attrs.push((modifier & 0xffff_ffff) as EGLint);
+                attrs.push(*modifier_hi as EGLint);
+                attrs.push((modifier >> 32) as EGLint);
+            }
+        }
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        unsafe {
+            EglImage::new(self, egl::NO_CONTEXT, egl::LINUX_DMA_BUF_EXT, std::ptr::null(), &attrs)
+        }
+    }
+}
+
+/// The GL object to create the [`EglImage`] from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlImageSource {
+    /// The `GL_TEXTURE_2D` texture with the given mipmap level.
+    ///
+    /// Requires `EGL_KHR_gl_texture_2D_image`.
+    Texture2D {
+        /// The name of the texture.
+        texture: u32,
+        /// The mipmap level of the texture.
+        level: u32,
+    },
+
+    /// The renderbuffer with the given name.
+    ///
+    /// Requires `EGL_KHR_gl_renderbuffer_image`.
+    Renderbuffer(u32),
+}
+
+impl PossiblyCurrentContext {
+    /// Create the [`EglImage`] sharing the storage of the given GL object
+    /// owned by this context.
+    pub fn create_image(&self, source: GlImageSource) -> Result<EglImage> {
+        let display = self.display();
+        let extensions = &display.inner.display_extensions;
+        if !display.inner.egl.CreateImageKHR.is_loaded() {
+            return Err(ErrorKind::NotSupported("EGL_KHR_image_base is not supported").into());
+        }
+
+        let mut attrs = Vec::<EGLint>::with_capacity(5);
+        let (target, buffer) = match source {
+            GlImageSource::Texture2D { texture, level } => {
+                if !extensions.contains("EGL_KHR_gl_texture_2D_image") {
+                    return Err(ErrorKind::NotSupported(
+                        "EGL_KHR_gl_texture_2D_image is not supported",
+                    )
+                    .into());
+                }
+
+                attrs.push(egl::GL_TEXTURE_LEVEL_KHR as EGLint);
+                attrs.push(level as EGLint);
+                (egl::GL_TEXTURE_2D_KHR, texture)
+            },
+            GlImageSource::Renderbuffer(renderbuffer) => {
+                if !extensions.contains("EGL_KHR_gl_renderbuffer_image") {
+                    return Err(ErrorKind::NotSupported(
+                        "EGL_KHR_gl_renderbuffer_image is not supported",
+                    )
+                    .into());
+                }
+
+                (egl::GL_RENDERBUFFER_KHR, renderbuffer)
+            },
+        };
+
+        // Push `egl::NONE` to terminate the list.
+        attrs.push(egl::NONE as EGLint);
+
+        let context = match self.raw_context() {
+            RawContext::Egl(context) => context,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        };
+
+        unsafe { EglImage::new(&display, context, target, buffer as usize as _, &attrs) }
+    }
+}
+
+/// A single plane of the DMA-BUF exported with [`EglImage::export_dmabuf`].
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct ExportedDmabufPlane {
+    /// The DMA-BUF file descriptor holding the plane.
+    pub fd: OwnedFd,
+    /// The offset of the plane in bytes from the start of the `fd`.
+    pub offset: u32,
+    /// The stride of the plane in bytes.
+    pub stride: u32,
+}
+
+/// The DMA-BUF exported with [`EglImage::export_dmabuf`].
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct ExportedDmabuf {
+    /// The `DRM_FORMAT_*` fourcc code of the buffer.
+    pub fourcc: u32,
+    /// The DRM format modifier of the buffer.
+    pub modifier: u64,
+    /// The planes of the buffer.
+    pub planes: Vec<ExportedDmabufPlane>,
+}
+
+/// A wrapper around the `EGLImage`.
+///
+/// The image holds the reference to the [`Display`] it was created with, so
+/// the display outlives it, and is destroyed on drop with `eglDestroyImageKHR`
+/// against that display. The other images and the objects created from the
+/// image, like the textures, stay valid.
+pub struct EglImage {
+    display: Display,
+    raw: EGLImageKHR,
+}
+
+// SAFETY: The `EGLImage` belongs to the display and could be used from any
+// thread.
+unsafe impl Send for EglImage {}
+unsafe impl Sync for EglImage {}
+
+impl EglImage {
+    /// # Safety
+    ///
+    /// The `context`, `target`, `buffer`, and `attrs` must form a valid
+    /// `eglCreateImageKHR` call and `attrs` must be terminated with
+    /// `EGL_NONE`.
+    pub(crate) unsafe fn new(
+        display: &Display,
+        context: egl::types::EGLContext,
+        target: EGLenum,
+        buffer: egl::types::EGLClientBuffer,
+        attrs: &[EGLint],
+    ) -> Result<Self> {
+        let raw = unsafe {
+            display.inner.egl.CreateImageKHR(
+                *display.inner.raw,
+                context,
+                target,
+                buffer,
+                attrs.as_ptr(),
+            )
+        };
+
+        if raw.is_null() {
+            Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadParameter.into()))
+        } else {
+            Ok(Self { display: display.clone(), raw })
+        }
+    }
+
+    /// Bind the image to the texture currently bound to the
+    /// `GL_TEXTURE_EXTERNAL_OES` target with `glEGLImageTargetTexture2DOES`.
+    ///
+    /// The `context` must be current on the calling thread.
+    pub fn bind_texture_external(&self, context: &PossiblyCurrentContext) -> Result<()> {
+        if !context.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        let name = CStr::from_bytes_with_nul(b"glEGLImageTargetTexture2DOES\0").unwrap();
+        let image_target_texture = self.display.get_proc_address(name);
+        if image_target_texture.is_null() {
+            return Err(
+                ErrorKind::NotSupported("GL_OES_EGL_image_external is not supported").into()
+            );
+        }
+
+        unsafe {
+            let image_target_texture: extern "system" fn(u32, *const ffi::c_void) =
+                std::mem::transmute(image_target_texture);
+            image_target_texture(TEXTURE_EXTERNAL_OES, self.raw);
+        }
+
+        Ok(())
+    }
+
+    /// Export the image as a DMA-BUF with `EGL_MESA_image_dma_buf_export`.
+    #[cfg(unix)]
+    pub fn export_dmabuf(&self) -> Result<ExportedDmabuf> {
+        let egl = self.display.inner.egl;
+        if !self.display.inner.display_extensions.contains("EGL_MESA_image_dma_buf_export") {
+            return Err(
+                ErrorKind::NotSupported("EGL_MESA_image_dma_buf_export is not supported").into()
+            );
+        }
+
+        let mut fourcc = 0;
+        let mut num_planes = 0;
+        let mut modifiers = [0; MAX_DMABUF_PLANES];
+        unsafe {
+            if egl.ExportDMABUFImageQueryMESA(
+                *self.display.inner.raw,
+                self.raw,
+                &mut fourcc,
+                &mut num_planes,
+                modifiers.as_mut_ptr(),
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap_or_else(|| ErrorKind::Misc.into()));
+            }
+        }
+
+        let num_planes = (num_planes as usize).min(MAX_DMABUF_PLANES);
+        let mut fds = [-1; MAX_DMABUF_PLANES];
+        let mut strides = [0; MAX_DMABUF_PLANES];
+        let mut offsets = [0; MAX_DMABUF_PLANES];
+        unsafe {
+            if egl.ExportDMABUFImageMESA(
+                *self.display.inner.raw,
+                self.raw,
+                fds.as_mut_ptr(),
+                strides.as_mut_ptr(),
+                offsets.as_mut_ptr(),
+            ) == egl::FALSE
+            {
+                return Err(super::check_error().err().unwrap_or_else(|| ErrorKind::Misc.into()));
+            }
+        }
+
+        // Planes sharing the same buffer could report `-1` for the fd, so reuse the
+        // fd of the first plane for them.
+        let mut planes = Vec::with_capacity(num_planes);
+        for plane in 0..num_planes {
+            let fd = if fds[plane] >= 0 {
+                unsafe { OwnedFd::from_raw_fd(fds[plane]) }
+            } else {
+                match planes.first() {
+                    Some(ExportedDmabufPlane { fd, .. }) => fd.try_clone().map_err(|err| {
+                        let raw_code = err.raw_os_error().map(|code| code as i64);
+                        crate::error::Error::new(raw_code, Some(err.to_string()), ErrorKind::Misc)
+                    })?,
+                    None => return Err(ErrorKind::BadParameter.into()),
+                }
+            };
+
+            planes.push(ExportedDmabufPlane {
+                fd,
+                offset: offsets[plane] as u32,
+                stride: strides[plane] as u32,
+            });
+        }
+
+        Ok(ExportedDmabuf { fourcc: fourcc as u32, modifier: modifiers[0], planes })
+    }
+
+    /// Get the raw `EGLImageKHR`.
+    pub fn raw_image(&self) -> EGLImageKHR {
+        self.raw
+    }
+}
+
+impl Drop for EglImage {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroyImageKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl GetGlDisplay for EglImage {
+    type Target = Display;
+
+    fn display(&self) -> Self::Target {
+        self.display.clone()
+    }
+}
+
+impl Sealed for EglImage {}
+
+impl fmt::Debug for EglImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EglImage").field("raw", &self.raw).finish()
+    }
+}
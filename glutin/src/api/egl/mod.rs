@@ -26,7 +26,13 @@ pub mod config;
 pub mod context;
 pub mod device;
 pub mod display;
+#[cfg(free_unix)]
+mod gbm;
+pub mod image;
 pub mod surface;
+pub mod sync;
+#[cfg(wayland_platform)]
+pub mod wayland;
 
 // WARNING: If this implementation is ever changed to unload or replace the
 // library, note that public API functions currently retirm `&'static str`ings
@@ -77,6 +83,9 @@ impl SymLoading for egl::Egl {
         egl::UnbindWaylandDisplayWL::load_with(loader);
         egl::QueryWaylandBufferWL::load_with(loader);
         egl::CreateWaylandBufferFromImageWL::load_with(loader);
+        egl::QueryDmaBufModifiersEXT::load_with(loader);
+        egl::GetNextFrameIdANDROID::load_with(loader);
+        egl::GetFrameTimestampsANDROID::load_with(loader);
 
         Self::load_with(loader)
     }
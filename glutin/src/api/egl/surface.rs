@@ -2,10 +2,12 @@
 
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+#[cfg(wayland_platform)]
+use std::ptr::NonNull;
 use std::{ffi, fmt};
 
 use glutin_egl_sys::egl;
-use glutin_egl_sys::egl::types::{EGLAttrib, EGLSurface, EGLint};
+use glutin_egl_sys::egl::types::{EGLAttrib, EGLSurface, EGLenum, EGLint};
 use raw_window_handle::RawWindowHandle;
 #[cfg(wayland_platform)]
 use wayland_sys::{egl::*, ffi_dispatch};
@@ -16,18 +18,109 @@ use crate::display::GetGlDisplay;
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
+#[cfg(free_unix)]
+use crate::surface::MappedBuffer;
 use crate::surface::{
-    AsRawSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface, Rect, SurfaceAttributes,
-    SurfaceTypeTrait, SwapInterval, WindowSurface,
+    AsRawSurface, GlFunctions, NativePixmap, PbufferSurface, PixmapSurface, RawSurface,
+    ReadPixelsFormat, Rect, SurfaceAttributes, SurfaceOrigin, SurfaceTypeTrait, SwapInterval,
+    TextureFormat, TextureTarget, WindowSurface,
 };
 
 use super::config::Config;
 use super::context::PossiblyCurrentContext;
 use super::display::Display;
+#[cfg(wayland_platform)]
+use super::wayland::FrameCallback;
 
 /// Hint for the attribute list size.
 const ATTR_SIZE_HINT: usize = 8;
 
+/// The state of the color buffer after the swap, see
+/// [`Surface::set_swap_behavior`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwapBehavior {
+    /// The color buffer is preserved, so the next frame could be drawn on top
+    /// of the previous one.
+    Preserved,
+
+    /// The color buffer contents are undefined after the swap, which is the
+    /// default for most implementations.
+    Destroyed,
+}
+
+/// The filter used to resolve the multisample buffer, see
+/// [`Surface::set_multisample_resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MultisampleResolve {
+    /// The filter chosen by the implementation.
+    Default,
+
+    /// The one pixel wide box filter, which weights all the samples equally.
+    Box,
+}
+
+/// The timestamp of the frame event, see [`FrameTimestamps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameTimestamp {
+    /// The event didn't happen yet, so the timestamps should be queried again
+    /// later.
+    Pending,
+
+    /// The event will never happen, e.g. the frame was dropped.
+    Invalid,
+
+    /// The time of the event in nanoseconds of the `CLOCK_MONOTONIC`.
+    Time(u64),
+}
+
+impl FrameTimestamp {
+    fn from_raw(value: i64) -> Self {
+        match value {
+            value if value == egl::TIMESTAMP_PENDING_ANDROID as i64 => Self::Pending,
+            value if value < 0 => Self::Invalid,
+            value => Self::Time(value as u64),
+        }
+    }
+}
+
+/// The timestamps of the frame from `EGL_ANDROID_get_frame_timestamps`, see
+/// [`Surface::frame_timestamps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameTimestamps {
+    /// The time the frame was requested to be presented at.
+    pub requested_present: FrameTimestamp,
+
+    /// The time the rendering of the frame was completed by the GPU.
+    pub rendering_complete: FrameTimestamp,
+
+    /// The time the compositor latched the frame.
+    pub composition_latch: FrameTimestamp,
+
+    /// The time the frame started to be displayed.
+    pub display_present: FrameTimestamp,
+}
+
+/// The buffer the client APIs render into, see [`Surface::render_buffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderBuffer {
+    /// The rendering goes directly into the visible buffer.
+    Single,
+
+    /// The rendering goes into the back buffer, which is presented on swap.
+    Back,
+}
+
+#[cfg(android_platform)]
+#[link(name = "android")]
+extern "C" {
+    fn ANativeWindow_setBuffersGeometry(
+        window: *mut ffi::c_void,
+        width: i32,
+        height: i32,
+        format: i32,
+    ) -> i32;
+}
+
 impl Display {
     pub(crate) unsafe fn create_pbuffer_surface(
         &self,
@@ -47,6 +140,33 @@ impl Display {
         attrs.push(egl::HEIGHT as EGLint);
         attrs.push(height.get() as EGLint);
 
+        // Fallback to the largest available size.
+        if surface_attributes.largest_pbuffer {
+            attrs.push(egl::LARGEST_PBUFFER as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
+        // Add render to texture attributes.
+        if let Some(texture_target) = surface_attributes.texture_target {
+            attrs.push(egl::TEXTURE_TARGET as EGLint);
+            attrs.push(match texture_target {
+                TextureTarget::Texture2D => egl::TEXTURE_2D as EGLint,
+            });
+        }
+
+        if let Some(texture_format) = surface_attributes.texture_format {
+            attrs.push(egl::TEXTURE_FORMAT as EGLint);
+            attrs.push(match texture_format {
+                TextureFormat::Rgb => egl::TEXTURE_RGB as EGLint,
+                TextureFormat::Rgba => egl::TEXTURE_RGBA as EGLint,
+            });
+        }
+
+        if surface_attributes.mipmaps {
+            attrs.push(egl::MIPMAP_TEXTURE as EGLint);
+            attrs.push(egl::TRUE as EGLint);
+        }
+
         // Push `egl::NONE` to terminate the list.
         attrs.push(egl::NONE as EGLint);
 
@@ -64,6 +184,7 @@ impl Display {
             native_window: None,
             config,
             raw: surface,
+            owned: true,
             _ty: PhantomData,
         })
     }
@@ -74,6 +195,7 @@ impl Display {
         surface_attributes: &SurfaceAttributes<PixmapSurface>,
     ) -> Result<Surface<PixmapSurface>> {
         let native_pixmap = surface_attributes.native_pixmap.as_ref().unwrap();
+        check_drm_format_modifier(config, surface_attributes.drm_format_modifier)?;
 
         let mut attrs = Vec::<EGLAttrib>::with_capacity(ATTR_SIZE_HINT);
 
@@ -153,6 +275,7 @@ impl Display {
             config,
             native_window: None,
             raw: surface,
+            owned: true,
             _ty: PhantomData,
         })
     }
@@ -162,6 +285,8 @@ impl Display {
         config: &Config,
         surface_attributes: &SurfaceAttributes<WindowSurface>,
     ) -> Result<Surface<WindowSurface>> {
+        check_drm_format_modifier(config, surface_attributes.drm_format_modifier)?;
+
         // Create native window.
         let native_window = NativeWindow::new(
             surface_attributes.width.unwrap(),
@@ -169,6 +294,69 @@ impl Display {
             surface_attributes.raw_window_handle.as_ref().unwrap(),
         )?;
 
+        unsafe { self.create_native_window_surface(config, surface_attributes, native_window) }
+    }
+
+    /// Create the window surface for the `wl_egl_window` created by the
+    /// caller, rather than the one glutin creates for the `wl_surface` of the
+    /// [`RawWindowHandle`] passed in the `surface_attributes`.
+    ///
+    /// This allows managing the `wl_egl_window` and its `wl_surface`
+    /// directly, e.g. for subsurfaces. The size in the `surface_attributes`
+    /// is ignored, while [`GlSurface::resize`] calls `wl_egl_window_resize`
+    /// on the given window. The `wl_egl_window` is not destroyed with the
+    /// surface.
+    ///
+    /// [`ErrorKind::NotSupported`] is returned when the [`RawWindowHandle`]
+    /// is not a Wayland one.
+    ///
+    /// # Safety
+    ///
+    /// The `wl_egl_window` must be a valid `wl_egl_window` created for the
+    /// `wl_surface` of the [`RawWindowHandle`], and it must outlive the
+    /// returned surface.
+    #[cfg(wayland_platform)]
+    pub unsafe fn create_window_surface_with_wl_egl_window(
+        &self,
+        config: &Config,
+        surface_attributes: &SurfaceAttributes<WindowSurface>,
+        wl_egl_window: NonNull<ffi::c_void>,
+    ) -> Result<Surface<WindowSurface>> {
+        let wl_surface = match surface_attributes.raw_window_handle {
+            Some(RawWindowHandle::Wayland(window_handle)) => window_handle.surface.as_ptr(),
+            _ => {
+                return Err(ErrorKind::NotSupported("the window handle is not a Wayland one").into())
+            },
+        };
+
+        let native_window = NativeWindow::Wayland {
+            wl_egl_window: wl_egl_window.as_ptr(),
+            wl_surface,
+            owned: false,
+        };
+        unsafe { self.create_native_window_surface(config, surface_attributes, native_window) }
+    }
+
+    /// # Safety
+    ///
+    /// The `native_window` must be valid.
+    unsafe fn create_native_window_surface(
+        &self,
+        config: &Config,
+        surface_attributes: &SurfaceAttributes<WindowSurface>,
+        native_window: NativeWindow,
+    ) -> Result<Surface<WindowSurface>> {
+        // The buffers of the `ANativeWindow` must match the format of the config, see
+        // the `EGL_NATIVE_VISUAL_ID` section of the `EGL_KHR_platform_android`.
+        #[cfg(android_platform)]
+        if let NativeWindow::Android(a_native_window) = native_window {
+            let format = config.native_visual() as i32;
+            // Zero sizes keep the current size of the window buffers.
+            if unsafe { ANativeWindow_setBuffersGeometry(a_native_window, 0, 0, format) } != 0 {
+                return Err(ErrorKind::BadNativeWindow.into());
+            }
+        }
+
         // XXX Window surface is using `EGLAttrib` and not `EGLint`.
         let mut attrs = Vec::<EGLAttrib>::with_capacity(ATTR_SIZE_HINT);
 
@@ -230,6 +418,55 @@ impl Display {
             config,
             native_window: Some(native_window),
             raw: surface,
+            owned: true,
+            _ty: PhantomData,
+        })
+    }
+
+    /// Wrap the `EGLSurface` created outside of glutin.
+    ///
+    /// The surface is not destroyed when the returned value is dropped, the
+    /// caller remains the owner of it.
+    ///
+    /// This function returns [`Err`] if the surface wasn't created with the
+    /// `config`. The surfaces unknown to this display are usually rejected
+    /// too, however the spec doesn't require EGL to detect them.
+    ///
+    /// # Safety
+    ///
+    /// - The `raw` must be a valid `EGLSurface` of type `T` created on this
+    ///   display, not on another one sharing the config.
+    /// - It must outlive the returned surface and must not be destroyed by
+    ///   anyone else while the surface is in use. The returned surface never
+    ///   destroys it, so it's not freed twice.
+    pub unsafe fn surface_from_raw<T: SurfaceTypeTrait>(
+        &self,
+        config: &Config,
+        raw: EGLSurface,
+    ) -> Result<Surface<T>> {
+        let mut config_id = 0;
+        if unsafe {
+            self.inner.egl.QuerySurface(
+                *self.inner.raw,
+                raw,
+                egl::CONFIG_ID as EGLint,
+                &mut config_id,
+            )
+        } == egl::FALSE
+        {
+            return Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadSurface.into()));
+        }
+
+        if config_id != config.config_id() {
+            return Err(ErrorKind::BadMatch.into());
+        }
+
+        Ok(Surface {
+            display: self.clone(),
+            config: config.clone(),
+            native_window: None,
+            raw,
+            owned: false,
             _ty: PhantomData,
         })
     }
@@ -243,12 +480,25 @@ impl Display {
     }
 }
 
+/// Ensure that the requested DRM format `modifier` is supported by the
+/// `config`.
+fn check_drm_format_modifier(config: &Config, modifier: Option<u64>) -> Result<()> {
+    match modifier {
+        Some(modifier) if !config.drm_format_modifiers().contains(&modifier) => {
+            Err(ErrorKind::BadMatch.into())
+        },
+        _ => Ok(()),
+    }
+}
+
 /// A wrapper around `EGLSurface`.
 pub struct Surface<T: SurfaceTypeTrait> {
     display: Display,
     config: Config,
     pub(crate) raw: EGLSurface,
     native_window: Option<NativeWindow>,
+    /// Whether the surface was created by glutin and should be destroyed.
+    owned: bool,
     _ty: PhantomData<T>,
 }
 
@@ -256,6 +506,20 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Query the config of the live surface with `EGL_CONFIG_ID`.
+    ///
+    /// Unlike [`GetGlConfig::config`], which returns the config the surface
+    /// was created with, the config is resolved by the implementation, so it
+    /// could be used to check the attributes actually granted to the surface,
+    /// like the number of samples.
+    pub fn query_config(&self) -> Result<Config> {
+        let config_id =
+            unsafe { self.query_attribute(egl::CONFIG_ID as EGLint) }.ok_or_else(|| {
+                super::check_error().err().unwrap_or_else(|| ErrorKind::BadSurface.into())
+            })?;
+        self.display.config_from_id(config_id).ok_or_else(|| ErrorKind::BadConfig.into())
+    }
+
     /// Swaps the underlying back buffers when the surface is not single
     /// buffered and pass the [`Rect`] information to the system
     /// compositor. Providing empty slice will damage the entire surface.
@@ -304,6 +568,101 @@ impl<T: SurfaceTypeTrait> Surface<T> {
         }
     }
 
+    /// Set whether the color buffer is preserved across swaps with
+    /// `EGL_SWAP_BEHAVIOR`.
+    ///
+    /// [`SwapBehavior::Preserved`] requires the config of the surface to
+    /// support it, see [`Config::supports_preserved_swap`], otherwise
+    /// [`ErrorKind::NotSupported`] is returned.
+    pub fn set_swap_behavior(&self, swap_behavior: SwapBehavior) -> Result<()> {
+        let value = match swap_behavior {
+            SwapBehavior::Preserved if !self.config.supports_preserved_swap() => {
+                return Err(ErrorKind::NotSupported(
+                    "the config doesn't support preserving the buffer on swap",
+                )
+                .into())
+            },
+            SwapBehavior::Preserved => egl::BUFFER_PRESERVED,
+            SwapBehavior::Destroyed => egl::BUFFER_DESTROYED,
+        };
+
+        let result = unsafe {
+            self.display.inner.egl.SurfaceAttrib(
+                *self.display.inner.raw,
+                self.raw,
+                egl::SWAP_BEHAVIOR as EGLint,
+                value as EGLint,
+            )
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Set the filter used to resolve the multisample buffer with
+    /// `EGL_MULTISAMPLE_RESOLVE`.
+    ///
+    /// [`MultisampleResolve::Box`] requires the config of the surface to
+    /// support it, see [`Config::supports_box_resolve`], otherwise
+    /// [`ErrorKind::NotSupported`] is returned.
+    pub fn set_multisample_resolve(&self, resolve: MultisampleResolve) -> Result<()> {
+        let value = match resolve {
+            MultisampleResolve::Box if !self.config.supports_box_resolve() => {
+                return Err(ErrorKind::NotSupported(
+                    "the config doesn't support the box multisample resolve",
+                )
+                .into())
+            },
+            MultisampleResolve::Box => egl::MULTISAMPLE_RESOLVE_BOX,
+            MultisampleResolve::Default => egl::MULTISAMPLE_RESOLVE_DEFAULT,
+        };
+
+        let result = unsafe {
+            self.display.inner.egl.SurfaceAttrib(
+                *self.display.inner.raw,
+                self.raw,
+                egl::MULTISAMPLE_RESOLVE as EGLint,
+                value as EGLint,
+            )
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The current `EGL_MULTISAMPLE_RESOLVE` of the surface.
+    pub fn multisample_resolve(&self) -> MultisampleResolve {
+        match unsafe { self.raw_attribute(egl::MULTISAMPLE_RESOLVE as EGLint) } as EGLenum {
+            egl::MULTISAMPLE_RESOLVE_BOX => MultisampleResolve::Box,
+            _ => MultisampleResolve::Default,
+        }
+    }
+
+    /// The `EGL_RENDER_BUFFER` of the surface.
+    ///
+    /// The value is queried every time, since it could be changed at runtime
+    /// with `EGL_KHR_mutable_render_buffer`.
+    pub fn render_buffer(&self) -> RenderBuffer {
+        match unsafe { self.raw_attribute(egl::RENDER_BUFFER as EGLint) } as EGLenum {
+            egl::SINGLE_BUFFER => RenderBuffer::Single,
+            _ => RenderBuffer::Back,
+        }
+    }
+
+    /// The current `EGL_SWAP_BEHAVIOR` of the surface.
+    pub fn swap_behavior(&self) -> SwapBehavior {
+        match unsafe { self.raw_attribute(egl::SWAP_BEHAVIOR as EGLint) } as EGLenum {
+            egl::BUFFER_PRESERVED => SwapBehavior::Preserved,
+            _ => SwapBehavior::Destroyed,
+        }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -319,12 +678,247 @@ impl<T: SurfaceTypeTrait> Surface<T> {
             value
         }
     }
+
+    /// Like [`Self::raw_attribute`], but returns `None` when `eglQuerySurface`
+    /// fails.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the attribute could be present.
+    unsafe fn query_attribute(&self, attr: EGLint) -> Option<EGLint> {
+        unsafe {
+            let mut value = 0;
+            if self.display.inner.egl.QuerySurface(
+                *self.display.inner.raw,
+                self.raw,
+                attr,
+                &mut value,
+            ) == egl::FALSE
+            {
+                None
+            } else {
+                Some(value)
+            }
+        }
+    }
+}
+
+impl Surface<PbufferSurface> {
+    /// Bind the color buffer of the pbuffer to the texture currently bound to
+    /// the texture target of the surface, with `eglBindTexImage`.
+    ///
+    /// The surface must be created with the texture target and format set,
+    /// see [`SurfaceAttributesBuilder::with_texture_target`], and a context
+    /// must be current on the calling thread.
+    ///
+    /// [`SurfaceAttributesBuilder::with_texture_target`]: crate::surface::SurfaceAttributesBuilder::with_texture_target
+    pub fn bind_tex_image(&self) -> Result<()> {
+        let result = unsafe {
+            self.display.inner.egl.BindTexImage(
+                *self.display.inner.raw,
+                self.raw,
+                egl::BACK_BUFFER as EGLint,
+            )
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Release the color buffer of the pbuffer bound with
+    /// [`Self::bind_tex_image`], with `eglReleaseTexImage`.
+    pub fn release_tex_image(&self) -> Result<()> {
+        let result = unsafe {
+            self.display.inner.egl.ReleaseTexImage(
+                *self.display.inner.raw,
+                self.raw,
+                egl::BACK_BUFFER as EGLint,
+            )
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(wayland_platform)]
+impl Surface<WindowSurface> {
+    /// Request the `wl_surface.frame` callback for the next frame.
+    ///
+    /// The request is sent along with the next commit of the surface, thus it
+    /// must be made before [`Self::swap_buffers`]. The returned
+    /// [`FrameCallback`] reports when the compositor thinks that it's a good
+    /// time to draw a new frame, which happens only when the application
+    /// dispatches the event queue of its `wl_surface`.
+    ///
+    /// Returns [`ErrorKind::NotSupported`] when the surface is not a Wayland
+    /// one.
+    pub fn request_frame_callback(&self) -> Result<FrameCallback> {
+        match self.native_window {
+            Some(NativeWindow::Wayland { wl_surface, .. }) => unsafe {
+                FrameCallback::new(wl_surface)
+            },
+            _ => {
+                Err(ErrorKind::NotSupported("frame callbacks are only available on Wayland").into())
+            },
+        }
+    }
+
+    /// Resize the surface like [`Self::resize`], additionally passing the
+    /// `dx` and `dy` offset of the new buffer relative to the current one to
+    /// `wl_egl_window_resize`.
+    ///
+    /// The offset keeps the content in place when the origin of the surface
+    /// moves, like when resizing from the top left edge.
+    ///
+    /// Returns [`ErrorKind::NotSupported`] when the surface is not a Wayland
+    /// one.
+    pub fn resize_with_offset(
+        &self,
+        _context: &PossiblyCurrentContext,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        dx: i32,
+        dy: i32,
+    ) -> Result<()> {
+        match self.native_window {
+            Some(ref native_window @ NativeWindow::Wayland { .. }) => {
+                native_window.resize(width, height, dx, dy);
+                Ok(())
+            },
+            _ => Err(ErrorKind::NotSupported("offsets are only available on Wayland").into()),
+        }
+    }
+}
+
+#[cfg(free_unix)]
+impl Surface<WindowSurface> {
+    /// Lock the front buffer of the GBM surface and map it for reading.
+    ///
+    /// See [`crate::surface::Surface::map_front_buffer`] for details.
+    pub fn map_front_buffer(&self) -> Option<MappedBuffer<'_>> {
+        match self.native_window {
+            Some(NativeWindow::Gbm(gbm_surface)) => unsafe {
+                super::gbm::map_front_buffer(gbm_surface)
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Surface<WindowSurface> {
+    /// Enable collecting the timestamps of the frames with
+    /// `EGL_ANDROID_get_frame_timestamps`, so they could be queried with
+    /// [`Self::frame_timestamps`].
+    ///
+    /// Returns [`ErrorKind::NotSupported`] when the extension is not
+    /// available.
+    pub fn enable_frame_timestamps(&self, enable: bool) -> Result<()> {
+        self.check_frame_timestamps()?;
+
+        let result = unsafe {
+            self.display.inner.egl.SurfaceAttrib(
+                *self.display.inner.raw,
+                self.raw,
+                egl::TIMESTAMPS_ANDROID as EGLint,
+                enable as EGLint,
+            )
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The id of the frame the next swap will present, with
+    /// `eglGetNextFrameIdANDROID`.
+    ///
+    /// It should be queried before [`Self::swap_buffers`] to get the
+    /// timestamps of that frame later.
+    pub fn next_frame_id(&self) -> Result<u64> {
+        self.check_frame_timestamps()?;
+
+        let mut frame_id = 0;
+        let result = unsafe {
+            self.display.inner.egl.GetNextFrameIdANDROID(
+                *self.display.inner.raw,
+                self.raw,
+                &mut frame_id,
+            )
+        };
+
+        if result == egl::FALSE {
+            Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadSurface.into()))
+        } else {
+            Ok(frame_id)
+        }
+    }
+
+    /// The timestamps of the frame with the given `frame_id`, with
+    /// `eglGetFrameTimestampsANDROID`.
+    ///
+    /// The collection of the timestamps must be enabled with
+    /// [`Self::enable_frame_timestamps`] before the frame is presented.
+    pub fn frame_timestamps(&self, frame_id: u64) -> Result<FrameTimestamps> {
+        self.check_frame_timestamps()?;
+
+        let names = [
+            egl::REQUESTED_PRESENT_TIME_ANDROID as EGLint,
+            egl::RENDERING_COMPLETE_TIME_ANDROID as EGLint,
+            egl::COMPOSITION_LATCH_TIME_ANDROID as EGLint,
+            egl::DISPLAY_PRESENT_TIME_ANDROID as EGLint,
+        ];
+        let mut values = [0i64; 4];
+        let result = unsafe {
+            self.display.inner.egl.GetFrameTimestampsANDROID(
+                *self.display.inner.raw,
+                self.raw,
+                frame_id,
+                names.len() as EGLint,
+                names.as_ptr(),
+                values.as_mut_ptr(),
+            )
+        };
+
+        if result == egl::FALSE {
+            return Err(super::check_error()
+                .err()
+                .unwrap_or_else(|| ErrorKind::BadParameter.into()));
+        }
+
+        Ok(FrameTimestamps {
+            requested_present: FrameTimestamp::from_raw(values[0]),
+            rendering_complete: FrameTimestamp::from_raw(values[1]),
+            composition_latch: FrameTimestamp::from_raw(values[2]),
+            display_present: FrameTimestamp::from_raw(values[3]),
+        })
+    }
+
+    fn check_frame_timestamps(&self) -> Result<()> {
+        if self.display.inner.display_extensions.contains("EGL_ANDROID_get_frame_timestamps")
+            && egl::GetFrameTimestampsANDROID::is_loaded()
+        {
+            Ok(())
+        } else {
+            Err(ErrorKind::NotSupported("EGL_ANDROID_get_frame_timestamps is not supported").into())
+        }
+    }
 }
 
 impl<T: SurfaceTypeTrait> Drop for Surface<T> {
     fn drop(&mut self) {
-        unsafe {
-            self.display.inner.egl.DestroySurface(*self.display.inner.raw, self.raw);
+        if self.owned {
+            unsafe {
+                self.display.inner.egl.DestroySurface(*self.display.inner.raw, self.raw);
+            }
         }
     }
 }
@@ -343,15 +937,30 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn width(&self) -> Option<u32> {
-        unsafe { Some(self.raw_attribute(egl::WIDTH as EGLint) as u32) }
+        unsafe { self.query_attribute(egl::WIDTH as EGLint).map(|width| width as u32) }
     }
 
     fn height(&self) -> Option<u32> {
-        unsafe { Some(self.raw_attribute(egl::HEIGHT as EGLint) as u32) }
+        unsafe { self.query_attribute(egl::HEIGHT as EGLint).map(|height| height as u32) }
     }
 
     fn is_single_buffered(&self) -> bool {
-        unsafe { self.raw_attribute(egl::RENDER_BUFFER as EGLint) == egl::SINGLE_BUFFER as i32 }
+        self.render_buffer() == RenderBuffer::Single
+    }
+
+    fn origin(&self) -> SurfaceOrigin {
+        if !self.display.inner.display_extensions.contains("EGL_ANGLE_surface_orientation") {
+            return SurfaceOrigin::BottomLeft;
+        }
+
+        match unsafe { self.query_attribute(egl::SURFACE_ORIENTATION_ANGLE as EGLint) } {
+            Some(orientation)
+                if orientation & egl::SURFACE_ORIENTATION_INVERT_Y_ANGLE as EGLint != 0 =>
+            {
+                SurfaceOrigin::TopLeft
+            },
+            _ => SurfaceOrigin::BottomLeft,
+        }
     }
 
     fn swap_buffers(&self, context: &Self::Context) -> Result<()> {
@@ -367,14 +976,30 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()> {
+        // `eglSwapInterval` applies to the draw surface of the current context, so make
+        // sure that it's the surface we were asked about.
+        if !context.is_current() || !self.is_current_draw(context) {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
         unsafe {
             context.inner.bind_api();
 
             let interval = match interval {
                 SwapInterval::DontWait => 0,
-                SwapInterval::Wait(interval) => interval.get() as EGLint,
+                SwapInterval::Wait(interval) => interval.get(),
             };
-            if self.display.inner.egl.SwapInterval(*self.display.inner.raw, interval) == egl::FALSE
+
+            // EGL silently clamps the interval to the range of the config, so reject it
+            // instead of presenting at the rate the user didn't ask for.
+            if interval < self.config.min_swap_interval()
+                || interval > self.config.max_swap_interval()
+            {
+                return Err(ErrorKind::BadParameter.into());
+            }
+
+            if self.display.inner.egl.SwapInterval(*self.display.inner.raw, interval as EGLint)
+                == egl::FALSE
             {
                 super::check_error()
             } else {
@@ -383,6 +1008,24 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn flush(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::flush(&self.display, context)
+    }
+
+    fn finish(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::finish(&self.display, context)
+    }
+
+    fn read_pixels(
+        &self,
+        context: &Self::Context,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>> {
+        let single_buffered = self.is_single_buffered();
+        GlFunctions::read_pixels(&self.display, context, single_buffered, rect, format)
+    }
+
     fn is_current(&self, context: &Self::Context) -> bool {
         self.is_current_draw(context) && self.is_current_read(context)
     }
@@ -402,7 +1045,7 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn resize(&self, _context: &Self::Context, width: NonZeroU32, height: NonZeroU32) {
-        self.native_window.as_ref().unwrap().resize(width, height)
+        self.native_window.as_ref().unwrap().resize(width, height, 0, 0)
     }
 }
 
@@ -435,6 +1078,7 @@ impl<T: SurfaceTypeTrait> fmt::Debug for Surface<T> {
             .field("config", &self.config.inner.raw)
             .field("raw", &self.raw)
             .field("native_window", &self.native_window)
+            .field("owned", &self.owned)
             .field("type", &T::surface_type())
             .finish()
     }
@@ -445,7 +1089,13 @@ impl<T: SurfaceTypeTrait> Sealed for Surface<T> {}
 #[derive(Debug)]
 enum NativeWindow {
     #[cfg(wayland_platform)]
-    Wayland(*mut ffi::c_void),
+    Wayland {
+        wl_egl_window: *mut ffi::c_void,
+        wl_surface: *mut ffi::c_void,
+        /// Whether the `wl_egl_window` was created by glutin and should be
+        /// destroyed.
+        owned: bool,
+    },
 
     #[cfg(x11_platform)]
     Xlib(std::os::raw::c_ulong),
@@ -482,7 +1132,11 @@ impl NativeWindow {
                 if ptr.is_null() {
                     return Err(ErrorKind::OutOfMemory.into());
                 }
-                Self::Wayland(ptr.cast())
+                Self::Wayland {
+                    wl_egl_window: ptr.cast(),
+                    wl_surface: window_handle.surface.as_ptr(),
+                    owned: true,
+                }
             },
             #[cfg(x11_platform)]
             RawWindowHandle::Xlib(window_handle) => {
@@ -512,18 +1166,18 @@ impl NativeWindow {
         Ok(native_window)
     }
 
-    fn resize(&self, _width: NonZeroU32, _height: NonZeroU32) {
+    fn resize(&self, _width: NonZeroU32, _height: NonZeroU32, _dx: i32, _dy: i32) {
         #[cfg(wayland_platform)]
-        if let Self::Wayland(wl_egl_surface) = self {
+        if let Self::Wayland { wl_egl_window, .. } = self {
             unsafe {
                 ffi_dispatch!(
                     wayland_egl_handle(),
                     wl_egl_window_resize,
-                    *wl_egl_surface as _,
+                    *wl_egl_window as _,
                     _width.get() as _,
                     _height.get() as _,
-                    0,
-                    0
+                    _dx,
+                    _dy
                 )
             }
         }
@@ -533,7 +1187,7 @@ impl NativeWindow {
     fn as_native_window(&self) -> egl::NativeWindowType {
         match *self {
             #[cfg(wayland_platform)]
-            Self::Wayland(wl_egl_surface) => wl_egl_surface,
+            Self::Wayland { wl_egl_window, .. } => wl_egl_window,
             #[cfg(x11_platform)]
             Self::Xlib(window_id) => window_id as egl::NativeWindowType,
             #[cfg(x11_platform)]
@@ -564,7 +1218,7 @@ impl NativeWindow {
     fn as_platform_window(&self) -> *mut ffi::c_void {
         match self {
             #[cfg(wayland_platform)]
-            Self::Wayland(wl_egl_surface) => *wl_egl_surface,
+            Self::Wayland { wl_egl_window, .. } => *wl_egl_window,
             #[cfg(x11_platform)]
             Self::Xlib(window_id) => window_id as *const _ as *mut ffi::c_void,
             #[cfg(x11_platform)]
@@ -583,7 +1237,7 @@ impl NativeWindow {
 impl Drop for NativeWindow {
     fn drop(&mut self) {
         unsafe {
-            if let Self::Wayland(wl_egl_window) = self {
+            if let Self::Wayland { wl_egl_window, owned: true, .. } = self {
                 ffi_dispatch!(wayland_egl_handle(), wl_egl_window_destroy, wl_egl_window.cast());
             }
         }
@@ -597,6 +1251,7 @@ impl NativePixmap {
             Self::XlibPixmap(xid) => xid as egl::NativePixmapType,
             Self::XcbPixmap(xid) => xid as egl::NativePixmapType,
             Self::WindowsPixmap(hbitmap) => hbitmap as egl::NativePixmapType,
+            Self::GbmBo(bo) => bo.as_ptr() as egl::NativePixmapType,
         }
     }
 
@@ -619,6 +1274,7 @@ impl NativePixmap {
             Self::XlibPixmap(xid) => xid as *const _ as *mut _,
             Self::XcbPixmap(xid) => xid as *const _ as *mut _,
             Self::WindowsPixmap(hbitmap) => *hbitmap as *const ffi::c_void as *mut _,
+            Self::GbmBo(bo) => bo.as_ptr(),
         }
     }
 }
@@ -0,0 +1,283 @@
+//! Everything related to `EGLSync`.
+//!
+//! The native fences allow synchronizing the rendering between processes: the
+//! producer exports the fence with [`EglSync::export_native_fence_fd`] after
+//! flushing, sends the file descriptor over a unix socket, and the consumer
+//! imports it with [`Display::import_native_fence_fd`] to wait on it before
+//! using the shared `EGLImage`. Both displays should use the same DRM render
+//! node, see [`Display::render_node_path`].
+
+use std::fmt;
+#[cfg(unix)]
+use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+use std::time::Duration;
+
+use glutin_egl_sys::egl;
+use glutin_egl_sys::egl::types::{EGLSyncKHR, EGLTimeKHR, EGLenum, EGLint};
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+
+/// The `EGL_NO_NATIVE_FENCE_FD_ANDROID` value.
+#[cfg(unix)]
+const NO_NATIVE_FENCE_FD: EGLint = -1;
+
+impl Display {
+    /// Insert a fence into the command stream of the context current on the
+    /// calling thread.
+    ///
+    /// The fence is signaled once all the commands issued before it are
+    /// completed.
+    ///
+    /// This function returns [`Err`] if `EGL_KHR_fence_sync` is not supported
+    /// or no context is current.
+    pub fn create_fence_sync(&self) -> Result<EglSync> {
+        if !self.inner.display_extensions.contains("EGL_KHR_fence_sync") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_fence_sync is not supported").into());
+        }
+
+        unsafe { EglSync::new(self, egl::SYNC_FENCE_KHR, &[egl::NONE as EGLint]) }
+    }
+
+    /// Create the sync signaled from the CPU with [`EglSync::signal`], which
+    /// could be reused by unsignaling it with [`EglSync::unsignal`].
+    ///
+    /// The sync is created unsignaled and could be waited on like the fence
+    /// one, e.g. by the GPU consumers with [`EglSync::server_wait`].
+    ///
+    /// This function returns [`Err`] if `EGL_KHR_reusable_sync` is not
+    /// supported.
+    pub fn create_reusable_sync(&self) -> Result<EglSync> {
+        if !self.inner.display_extensions.contains("EGL_KHR_reusable_sync") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_reusable_sync is not supported").into());
+        }
+
+        unsafe { EglSync::new(self, egl::SYNC_REUSABLE_KHR, &[egl::NONE as EGLint]) }
+    }
+
+    /// Insert a native fence into the command stream of the context current on
+    /// the calling thread.
+    ///
+    /// The fence could be exported with [`EglSync::export_native_fence_fd`] to
+    /// hand it over to other processes, like a compositor.
+    ///
+    /// This function returns [`Err`] if `EGL_ANDROID_native_fence_sync` is not
+    /// supported or no context is current.
+    #[cfg(unix)]
+    pub fn create_native_fence_sync(&self) -> Result<EglSync> {
+        if !self.inner.display_extensions.contains("EGL_ANDROID_native_fence_sync") {
+            return Err(
+                ErrorKind::NotSupported("EGL_ANDROID_native_fence_sync is not supported").into()
+            );
+        }
+
+        let attrs =
+            [egl::SYNC_NATIVE_FENCE_FD_ANDROID as EGLint, NO_NATIVE_FENCE_FD, egl::NONE as EGLint];
+        unsafe { EglSync::new(self, egl::SYNC_NATIVE_FENCE_ANDROID, &attrs) }
+    }
+
+    /// Import the native fence file descriptor exported by
+    /// [`EglSync::export_native_fence_fd`], possibly in another process.
+    ///
+    /// The returned sync is signaled when the fence is, so the current context
+    /// could wait on it with [`EglSync::server_wait`]. The `fd` is owned by
+    /// EGL afterwards, and closed when the sync couldn't be created.
+    ///
+    /// This function returns [`Err`] if `EGL_ANDROID_native_fence_sync` is not
+    /// supported or no context is current.
+    #[cfg(unix)]
+    pub fn import_native_fence_fd(&self, fd: OwnedFd) -> Result<EglSync> {
+        if !self.inner.display_extensions.contains("EGL_ANDROID_native_fence_sync") {
+            return Err(
+                ErrorKind::NotSupported("EGL_ANDROID_native_fence_sync is not supported").into()
+            );
+        }
+
+        let attrs =
+            [egl::SYNC_NATIVE_FENCE_FD_ANDROID as EGLint, fd.as_raw_fd(), egl::NONE as EGLint];
+        let sync = unsafe { EglSync::new(self, egl::SYNC_NATIVE_FENCE_ANDROID, &attrs)? };
+
+        // EGL took the ownership of the fd.
+        let _ = fd.into_raw_fd();
+
+        Ok(sync)
+    }
+}
+
+/// The status of the [`EglSync`] after waiting on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// The sync was signaled.
+    Signaled,
+
+    /// The timeout expired before the sync was signaled.
+    TimedOut,
+}
+
+/// A wrapper around the `EGLSync`.
+///
+/// The sync is destroyed on drop.
+pub struct EglSync {
+    display: Display,
+    raw: EGLSyncKHR,
+}
+
+// SAFETY: The `EGLSync` belongs to the display and could be waited on from any
+// thread.
+unsafe impl Send for EglSync {}
+unsafe impl Sync for EglSync {}
+
+impl EglSync {
+    /// # Safety
+    ///
+    /// The `attrs` must be terminated with `EGL_NONE` and be valid for the
+    /// given `ty`.
+    unsafe fn new(display: &Display, ty: EGLenum, attrs: &[EGLint]) -> Result<Self> {
+        let egl = display.inner.egl;
+        let raw = unsafe { egl.CreateSyncKHR(*display.inner.raw, ty, attrs.as_ptr()) };
+        if raw.is_null() {
+            Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadParameter.into()))
+        } else {
+            Ok(Self { display: display.clone(), raw })
+        }
+    }
+
+    /// Block the calling thread until the sync is signaled.
+    ///
+    /// The commands of the current context are flushed before waiting.
+    pub fn wait(&self) -> Result<()> {
+        let result = unsafe {
+            self.display.inner.egl.ClientWaitSyncKHR(
+                *self.display.inner.raw,
+                self.raw,
+                egl::SYNC_FLUSH_COMMANDS_BIT_KHR as EGLint,
+                egl::FOREVER_KHR,
+            )
+        };
+
+        if result == egl::FALSE as EGLint {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Block the calling thread until the sync is signaled or the `timeout`
+    /// expires.
+    ///
+    /// The commands of the current context are flushed before waiting. The
+    /// `timeout` has nanosecond granularity, the values not fitting into it
+    /// wait forever.
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<SyncStatus> {
+        let timeout = EGLTimeKHR::try_from(timeout.as_nanos()).unwrap_or(egl::FOREVER_KHR);
+        self.client_wait(timeout)
+    }
+
+    /// Check whether the sync is signaled without blocking.
+    ///
+    /// The commands of the current context are flushed, so the sync is
+    /// guaranteed to get signaled eventually.
+    pub fn is_signaled(&self) -> Result<bool> {
+        self.client_wait(0).map(|status| status == SyncStatus::Signaled)
+    }
+
+    fn client_wait(&self, timeout: EGLTimeKHR) -> Result<SyncStatus> {
+        let result = unsafe {
+            self.display.inner.egl.ClientWaitSyncKHR(
+                *self.display.inner.raw,
+                self.raw,
+                egl::SYNC_FLUSH_COMMANDS_BIT_KHR as EGLint,
+                timeout,
+            )
+        };
+
+        match result as EGLenum {
+            egl::CONDITION_SATISFIED_KHR => Ok(SyncStatus::Signaled),
+            egl::TIMEOUT_EXPIRED_KHR => Ok(SyncStatus::TimedOut),
+            _ => Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadParameter.into())),
+        }
+    }
+
+    /// Make the GPU wait for the sync to be signaled before executing further
+    /// commands of the context current on the calling thread.
+    ///
+    /// Unlike [`Self::wait`] this function doesn't block the calling thread.
+    ///
+    /// This function returns [`Err`] if `EGL_KHR_wait_sync` is not supported.
+    pub fn server_wait(&self) -> Result<()> {
+        if !self.display.inner.display_extensions.contains("EGL_KHR_wait_sync") {
+            return Err(ErrorKind::NotSupported("EGL_KHR_wait_sync is not supported").into());
+        }
+
+        let result =
+            unsafe { self.display.inner.egl.WaitSyncKHR(*self.display.inner.raw, self.raw, 0) };
+
+        if result == egl::FALSE as EGLint {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Signal the sync created with [`Display::create_reusable_sync`],
+    /// unblocking all the waiters.
+    pub fn signal(&self) -> Result<()> {
+        self.set_status(egl::SIGNALED_KHR)
+    }
+
+    /// Unsignal the sync created with [`Display::create_reusable_sync`], so it
+    /// could be waited on again.
+    pub fn unsignal(&self) -> Result<()> {
+        self.set_status(egl::UNSIGNALED_KHR)
+    }
+
+    fn set_status(&self, mode: EGLenum) -> Result<()> {
+        let result = unsafe {
+            self.display.inner.egl.SignalSyncKHR(*self.display.inner.raw, self.raw, mode)
+        };
+
+        if result == egl::FALSE {
+            super::check_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Export the native fence file descriptor of the sync created with
+    /// [`Display::create_native_fence_sync`].
+    ///
+    /// The fence becomes valid only after the commands of the context are
+    /// flushed.
+    #[cfg(unix)]
+    pub fn export_native_fence_fd(&self) -> Result<OwnedFd> {
+        let fd = unsafe {
+            self.display.inner.egl.DupNativeFenceFDANDROID(*self.display.inner.raw, self.raw)
+        };
+
+        if fd == NO_NATIVE_FENCE_FD {
+            Err(super::check_error().err().unwrap_or_else(|| ErrorKind::BadParameter.into()))
+        } else {
+            Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+        }
+    }
+
+    /// Get the raw `EGLSyncKHR`.
+    pub fn raw_sync(&self) -> EGLSyncKHR {
+        self.raw
+    }
+}
+
+impl Drop for EglSync {
+    fn drop(&mut self) {
+        unsafe {
+            self.display.inner.egl.DestroySyncKHR(*self.display.inner.raw, self.raw);
+        }
+    }
+}
+
+impl fmt::Debug for EglSync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EglSync").field("raw", &self.raw).finish()
+    }
+}
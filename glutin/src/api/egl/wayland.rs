@@ -0,0 +1,112 @@
+//! Wayland frame callbacks.
+//!
+//! The `wl_callback` interface is described manually, since it's not provided
+//! by `wayland-sys` and we don't want to depend on the full `wayland-client`.
+
+use std::os::raw::{c_char, c_int};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{ffi, ptr};
+
+use wayland_sys::client::*;
+use wayland_sys::common::{wl_interface, wl_message};
+use wayland_sys::ffi_dispatch;
+
+use crate::error::{ErrorKind, Result};
+
+/// The opcode of the `wl_surface.frame` request.
+const WL_SURFACE_FRAME: u32 = 3;
+
+/// Wrapper to put the interface description into statics.
+#[repr(transparent)]
+struct SyncWrapper<T>(T);
+
+unsafe impl<T> Sync for SyncWrapper<T> {}
+
+static WL_CALLBACK_DONE_TYPES: SyncWrapper<[*const wl_interface; 1]> = SyncWrapper([ptr::null()]);
+
+static WL_CALLBACK_EVENTS: SyncWrapper<[wl_message; 1]> = SyncWrapper([wl_message {
+    name: b"done\0".as_ptr() as *const c_char,
+    signature: b"u\0".as_ptr() as *const c_char,
+    types: &WL_CALLBACK_DONE_TYPES.0 as *const _ as *const *const wl_interface,
+}]);
+
+static WL_CALLBACK_INTERFACE: SyncWrapper<wl_interface> = SyncWrapper(wl_interface {
+    name: b"wl_callback\0".as_ptr() as *const c_char,
+    version: 1,
+    request_count: 0,
+    requests: ptr::null(),
+    event_count: 1,
+    events: &WL_CALLBACK_EVENTS.0 as *const _ as *const wl_message,
+});
+
+#[repr(C)]
+struct WlCallbackListener {
+    done: unsafe extern "C" fn(*mut ffi::c_void, *mut wl_proxy, u32),
+}
+
+static WL_CALLBACK_LISTENER: WlCallbackListener = WlCallbackListener { done: callback_done };
+
+unsafe extern "C" fn callback_done(data: *mut ffi::c_void, callback: *mut wl_proxy, _time: u32) {
+    unsafe {
+        let done = Arc::from_raw(data as *const AtomicBool);
+        done.store(true, Ordering::Release);
+        ffi_dispatch!(wayland_client_handle(), wl_proxy_destroy, callback);
+    }
+}
+
+/// The token for the requested `wl_surface.frame` callback.
+///
+/// The callback is delivered on the event queue of the `wl_surface`, so the
+/// token is updated only when the application dispatches that queue.
+#[derive(Debug, Clone)]
+pub struct FrameCallback {
+    done: Arc<AtomicBool>,
+}
+
+impl FrameCallback {
+    /// Request the frame callback on the given `wl_surface`.
+    ///
+    /// # Safety
+    ///
+    /// The `wl_surface` must be a valid pointer.
+    pub(crate) unsafe fn new(wl_surface: *mut ffi::c_void) -> Result<Self> {
+        let done = Arc::new(AtomicBool::new(false));
+        unsafe {
+            let callback = ffi_dispatch!(
+                wayland_client_handle(),
+                wl_proxy_marshal_constructor,
+                wl_surface as *mut wl_proxy,
+                WL_SURFACE_FRAME,
+                &WL_CALLBACK_INTERFACE.0 as *const wl_interface,
+                ptr::null_mut::<ffi::c_void>()
+            );
+            if callback.is_null() {
+                return Err(ErrorKind::OutOfMemory.into());
+            }
+
+            let data = Arc::into_raw(done.clone()) as *mut ffi::c_void;
+            let listener = &WL_CALLBACK_LISTENER as *const _ as *mut extern "C" fn();
+            let res: c_int = ffi_dispatch!(
+                wayland_client_handle(),
+                wl_proxy_add_listener,
+                callback,
+                listener,
+                data
+            );
+            if res != 0 {
+                drop(Arc::from_raw(data as *const AtomicBool));
+                ffi_dispatch!(wayland_client_handle(), wl_proxy_destroy, callback);
+                return Err(ErrorKind::Misc.into());
+            }
+        }
+
+        Ok(Self { done })
+    }
+
+    /// Whether the compositor has sent the `done` event, meaning that it's a
+    /// good time to draw the next frame.
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+}
@@ -7,14 +7,16 @@ use std::{fmt, slice};
 
 use glutin_glx_sys::glx::types::GLXFBConfig;
 use glutin_glx_sys::{glx, glx_extra};
+use once_cell::sync::OnceCell;
 use raw_window_handle::RawWindowHandle;
 
 use crate::config::{
-    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, RawConfig,
+    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, NativeVisual,
+    RawConfig,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
-use crate::platform::x11::{X11GlConfigExt, X11VisualInfo, XLIB};
+use crate::platform::x11::{self, X11GlConfigExt, X11VisualInfo, XLIB};
 use crate::private::Sealed;
 
 use super::display::Display;
@@ -95,12 +97,24 @@ impl Display {
         config_attributes.push(glx::STENCIL_SIZE as c_int);
         config_attributes.push(template.stencil_size as c_int);
 
-        // Add visual if was provided.
-        if let Some(RawWindowHandle::Xlib(window)) = template.native_window {
-            if window.visual_id > 0 {
-                config_attributes.push(glx::VISUAL_ID as c_int);
-                config_attributes.push(window.visual_id as c_int);
-            }
+        // Add visual if was provided, or query it from the window otherwise.
+        let visual_id = match template.native_window {
+            Some(RawWindowHandle::Xlib(window)) if window.visual_id > 0 => {
+                Some(window.visual_id as u32)
+            },
+            Some(RawWindowHandle::Xlib(window)) => unsafe {
+                x11::window_visual_id(self.inner.raw.cast(), window.window)
+            },
+            Some(RawWindowHandle::Xcb(window)) => {
+                window.visual_id.map(|id| id.get()).or_else(|| unsafe {
+                    x11::window_visual_id(self.inner.raw.cast(), window.window.get() as _)
+                })
+            },
+            _ => None,
+        };
+        if let Some(visual_id) = visual_id {
+            config_attributes.push(glx::VISUAL_ID as c_int);
+            config_attributes.push(visual_id as c_int);
         }
 
         // Add surface type.
@@ -170,18 +184,47 @@ impl Display {
                 .into_iter()
                 .map(move |raw| {
                     let raw = GlxConfig(raw);
-                    let inner = Arc::new(ConfigInner { display: self.clone(), raw });
+                    let inner = Arc::new(ConfigInner::new(self.clone(), raw));
                     Config { inner }
                 })
                 .filter(move |config| {
                     !template.transparency || config.supports_transparency().unwrap_or(false)
-                });
+                })
+                .filter(move |config| template.within_max_sizes(config));
 
             Ok(Box::new(iter))
         }
     }
 }
 
+impl Display {
+    /// Find the config with the given `GLX_FBCONFIG_ID`.
+    pub(crate) fn config_from_id(&self, config_id: c_int) -> Option<Config> {
+        let attrs = [glx::FBCONFIG_ID as c_int, config_id, 0];
+        unsafe {
+            let mut num_configs = 0;
+            let raw_configs = self.inner.glx.ChooseFBConfig(
+                self.inner.raw.cast(),
+                self.inner.screen as _,
+                attrs.as_ptr() as *const _,
+                &mut num_configs,
+            );
+
+            if raw_configs.is_null() {
+                return None;
+            }
+
+            let raw = (num_configs > 0).then(|| *raw_configs);
+
+            // Free the memory from the Xlib, since we've just copied it.
+            (XLIB.as_ref().unwrap().XFree)(raw_configs as *mut _);
+
+            let inner = Arc::new(ConfigInner::new(self.clone(), GlxConfig(raw?)));
+            Some(Config { inner })
+        }
+    }
+}
+
 /// A wrapper around `GLXFBConfig`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Config {
@@ -189,6 +232,11 @@ pub struct Config {
 }
 
 impl Config {
+    /// The `GLX_FBCONFIG_ID` of the config.
+    pub(crate) fn config_id(&self) -> c_int {
+        unsafe { self.raw_attribute(glx::FBCONFIG_ID as c_int) }
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -269,6 +317,10 @@ impl GlConfig for Config {
         unsafe { self.raw_attribute(glx::SAMPLES as c_int) as u8 }
     }
 
+    fn num_sample_buffers(&self) -> u8 {
+        unsafe { self.raw_attribute(glx::SAMPLE_BUFFERS as c_int) as u8 }
+    }
+
     fn config_surface_types(&self) -> ConfigSurfaceTypes {
         let mut ty = ConfigSurfaceTypes::empty();
 
@@ -286,23 +338,29 @@ impl GlConfig for Config {
         ty
     }
 
-    fn supports_transparency(&self) -> Option<bool> {
-        self.x11_visual().map(|visual| visual.supports_transparency())
+    fn max_pbuffer_width(&self) -> u32 {
+        unsafe { self.raw_attribute(glx::MAX_PBUFFER_WIDTH as c_int) as u32 }
     }
 
-    fn api(&self) -> Api {
-        let mut api = Api::OPENGL;
-        if self.inner.display.inner.features.contains(DisplayFeatures::CREATE_ES_CONTEXT) {
-            api |= Api::GLES1 | Api::GLES2;
-        }
+    fn max_pbuffer_height(&self) -> u32 {
+        unsafe { self.raw_attribute(glx::MAX_PBUFFER_HEIGHT as c_int) as u32 }
+    }
 
-        api
+    fn max_pbuffer_pixels(&self) -> u32 {
+        unsafe { self.raw_attribute(glx::MAX_PBUFFER_PIXELS as c_int) as u32 }
     }
-}
 
-impl X11GlConfigExt for Config {
-    fn x11_visual(&self) -> Option<X11VisualInfo> {
-        unsafe {
+    fn supports_transparency(&self) -> Option<bool> {
+        match self.native_visual_info()? {
+            NativeVisual::X11 { alpha_mask, .. } => Some(alpha_mask != 0),
+            _ => None,
+        }
+    }
+
+    fn native_visual_info(&self) -> Option<NativeVisual> {
+        // The visual is resolved once, since it requires a round-trip to the
+        // server.
+        *self.inner.native_visual.get_or_init(|| unsafe {
             let raw_visual = self
                 .inner
                 .display
@@ -312,12 +370,40 @@ impl X11GlConfigExt for Config {
             if raw_visual.is_null() {
                 None
             } else {
-                Some(X11VisualInfo::from_raw(
+                let visual = X11VisualInfo::from_raw(
                     self.inner.display.inner.raw.cast(),
                     raw_visual as *mut _,
-                ))
+                );
+                Some(visual.native_visual())
             }
+        })
+    }
+
+    fn api(&self) -> Api {
+        let mut api = Api::OPENGL;
+        if self.inner.display.inner.features.contains(DisplayFeatures::CREATE_ES_CONTEXT) {
+            api |= Api::GLES1 | Api::GLES2;
         }
+
+        api
+    }
+}
+
+impl X11GlConfigExt for Config {
+    fn x11_visual(&self) -> Option<X11VisualInfo> {
+        let visual_id = match self.native_visual_info()? {
+            NativeVisual::X11 { visual_id, .. } => visual_id,
+            _ => return None,
+        };
+
+        // Looking up the visual by its id is handled by Xlib without the
+        // server.
+        unsafe { X11VisualInfo::from_xid(self.inner.display.inner.raw.cast(), visual_id as _) }
+    }
+
+    fn x11_visual_id(&self) -> Option<u32> {
+        let xid = unsafe { self.raw_attribute(glx::VISUAL_ID as c_int) };
+        (xid != 0).then_some(xid as u32)
     }
 }
 
@@ -340,6 +426,14 @@ impl Sealed for Config {}
 pub(crate) struct ConfigInner {
     display: Display,
     pub(crate) raw: GlxConfig,
+    /// The visual of the config, resolved on the first use.
+    native_visual: OnceCell<Option<NativeVisual>>,
+}
+
+impl ConfigInner {
+    fn new(display: Display, raw: GlxConfig) -> Self {
+        Self { display, raw, native_visual: OnceCell::new() }
+    }
 }
 
 impl PartialEq for ConfigInner {
@@ -11,13 +11,14 @@ use glutin_glx_sys::{glx, glx_extra};
 use crate::config::GetGlConfig;
 use crate::context::{
     self, AsRawContext, ContextApi, ContextAttributes, GlProfile, RawContext, ReleaseBehavior,
-    Robustness, Version,
+    ResetNotification, Robustness, Version,
 };
+use crate::current_guard;
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
-use crate::surface::SurfaceTypeTrait;
+use crate::surface::{RawSurface, SurfaceTypeTrait};
 
 use super::config::Config;
 use super::display::Display;
@@ -29,6 +30,8 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.validate()?;
+
         let shared_context = if let Some(shared_context) =
             context_attributes.shared_context.as_ref()
         {
@@ -41,12 +44,24 @@ impl Display {
             std::ptr::null()
         };
 
-        let context = if self.inner.client_extensions.contains("GLX_ARB_create_context")
+        let (context, version) = if self.inner.client_extensions.contains("GLX_ARB_create_context")
             && self.inner.glx_extra.is_some()
         {
-            self.create_context_arb(config, context_attributes, shared_context)?
+            let result = context_attributes.with_highest_core_version(|attrs| {
+                match self.create_context_arb(config, attrs, shared_context)? {
+                    context if context.is_null() => Err(ErrorKind::BadContext.into()),
+                    context => Ok(context),
+                }
+            });
+            match result {
+                Some((context, version)) => (context, Some(version)),
+                None => (
+                    self.create_context_arb(config, context_attributes, shared_context)?,
+                    context_attributes.api.and_then(|api| api.version()),
+                ),
+            }
         } else {
-            self.create_context_legacy(config, shared_context)?
+            (self.create_context_legacy(config, shared_context)?, None)
         };
 
         // Failed to create the context.
@@ -56,8 +71,13 @@ impl Display {
 
         let config = config.clone();
         let is_gles = matches!(context_attributes.api, Some(ContextApi::Gles(_)));
-        let inner =
-            ContextInner { display: self.clone(), config, raw: GlxContext(context), is_gles };
+        let inner = ContextInner {
+            display: self.clone(),
+            config,
+            raw: GlxContext(context),
+            is_gles,
+            version,
+        };
 
         Ok(NotCurrentContext::new(inner))
     }
@@ -126,14 +146,7 @@ impl Display {
         if self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS) {
             match context_attributes.robustness {
                 Robustness::NotRobust => (),
-                Robustness::RobustNoResetNotification => {
-                    attrs.push(glx_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
-                    attrs.push(glx_extra::NO_RESET_NOTIFICATION_ARB as c_int);
-                    flags |= glx_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
-                },
-                Robustness::RobustLoseContextOnReset => {
-                    attrs.push(glx_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
-                    attrs.push(glx_extra::LOSE_CONTEXT_ON_RESET_ARB as c_int);
+                Robustness::RobustNoResetNotification | Robustness::RobustLoseContextOnReset => {
                     flags |= glx_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
                 },
                 Robustness::NoError => {
@@ -144,12 +157,38 @@ impl Display {
                         .into());
                     }
 
+                    // The no error contexts can't have the debug or robust access flags, or the
+                    // reset notification strategy, according to the spec.
+                    if context_attributes.debug {
+                        return Err(ErrorKind::InvalidContextAttributes(
+                            "the no error context can't be the debug one",
+                        )
+                        .into());
+                    }
+                    if context_attributes.reset_notification.is_some() {
+                        return Err(ErrorKind::InvalidContextAttributes(
+                            "the no error context can't have the reset notification strategy",
+                        )
+                        .into());
+                    }
+
                     attrs.push(glx_extra::CONTEXT_OPENGL_NO_ERROR_ARB as c_int);
                     attrs.push(1);
                     requested_no_error = true;
                 },
             }
-        } else if context_attributes.robustness != Robustness::NotRobust {
+
+            if let Some(reset_notification) = context_attributes.reset_notification() {
+                let strategy = match reset_notification {
+                    ResetNotification::NoResetNotification => glx_extra::NO_RESET_NOTIFICATION_ARB,
+                    ResetNotification::LoseContextOnReset => glx_extra::LOSE_CONTEXT_ON_RESET_ARB,
+                };
+                attrs.push(glx_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
+                attrs.push(strategy as c_int);
+            }
+        } else if context_attributes.robustness != Robustness::NotRobust
+            || context_attributes.reset_notification.is_some()
+        {
             return Err(ErrorKind::NotSupported(
                 "GLX_ARB_create_context_robustness is not supported",
             )
@@ -161,6 +200,13 @@ impl Display {
             flags |= glx_extra::CONTEXT_DEBUG_BIT_ARB as c_int;
         }
 
+        // Forward compatible flag, which only applies to desktop OpenGL.
+        if context_attributes.forward_compatible
+            && !matches!(context_attributes.api, Some(ContextApi::Gles(_)))
+        {
+            flags |= glx_extra::CONTEXT_FORWARD_COMPATIBLE_BIT_ARB as c_int;
+        }
+
         if flags != 0 {
             attrs.push(glx_extra::CONTEXT_FLAGS_ARB as c_int);
             attrs.push(flags as c_int);
@@ -249,10 +295,10 @@ impl NotCurrentGlContext for NotCurrentContext {
         Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<Self::PossiblyCurrentContext> {
         self.inner.make_current_draw_read(surface_draw, surface_read)?;
         Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
@@ -310,14 +356,27 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
         unsafe { self.inner.display.inner.glx.GetCurrentContext() == *self.inner.raw }
     }
 
+    fn query_current_surface(&self) -> Option<(RawSurface, RawSurface)> {
+        if !self.is_current() {
+            return None;
+        }
+
+        let glx = self.inner.display.inner.glx;
+        unsafe {
+            let draw = glx.GetCurrentDrawable();
+            let read = glx.GetCurrentReadDrawable();
+            Some((RawSurface::Glx(draw as u64), RawSurface::Glx(read as u64)))
+        }
+    }
+
     fn make_current<T: SurfaceTypeTrait>(&self, surface: &Self::Surface<T>) -> Result<()> {
         self.inner.make_current_draw_read(surface, surface)
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<()> {
         self.inner.make_current_draw_read(surface_draw, surface_read)
     }
@@ -358,14 +417,17 @@ struct ContextInner {
     config: Config,
     raw: GlxContext,
     is_gles: bool,
+    /// The version requested when creating the context, if known.
+    version: Option<Version>,
 }
 
 impl ContextInner {
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Surface<T>,
-        surface_read: &Surface<T>,
+        surface_read: &Surface<U>,
     ) -> Result<()> {
+        current_guard::check_make_current(*self.raw as usize);
         super::last_glx_error(|| unsafe {
             self.display.inner.glx.MakeContextCurrent(
                 self.display.inner.raw.cast(),
@@ -373,7 +435,9 @@ impl ContextInner {
                 surface_read.raw,
                 *self.raw,
             );
-        })
+        })?;
+        current_guard::mark_current(*self.raw as usize, 0);
+        Ok(())
     }
 
     fn make_not_current(&self) -> Result<()> {
@@ -384,14 +448,16 @@ impl ContextInner {
                 0,
                 std::ptr::null(),
             );
-        })
+        })?;
+        current_guard::mark_not_current(0);
+        Ok(())
     }
 
     fn context_api(&self) -> ContextApi {
         if self.is_gles {
-            ContextApi::Gles(None)
+            ContextApi::Gles(self.version)
         } else {
-            ContextApi::OpenGl(None)
+            ContextApi::OpenGl(self.version)
         }
     }
 }
@@ -401,6 +467,7 @@ impl Drop for ContextInner {
         let _ = super::last_glx_error(|| unsafe {
             self.display.inner.glx.DestroyContext(self.display.inner.raw.cast(), *self.raw);
         });
+        current_guard::forget(*self.raw as usize);
     }
 }
 
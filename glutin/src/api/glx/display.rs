@@ -4,6 +4,7 @@ use std::collections::HashSet;
 use std::ffi::{self, CStr};
 use std::fmt;
 use std::ops::Deref;
+use std::os::raw::c_int;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
@@ -13,7 +14,9 @@ use raw_window_handle::RawDisplayHandle;
 
 use crate::config::ConfigTemplate;
 use crate::context::Version;
-use crate::display::{AsRawDisplay, DisplayFeatures, GetDisplayExtensions, RawDisplay};
+use crate::display::{
+    AsRawDisplay, DisplayFeatures, GetDisplayExtensions, ProcLoader, ProcLoaderCell, RawDisplay,
+};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
@@ -105,16 +108,54 @@ impl Display {
             screen,
             features,
             client_extensions,
+            proc_loader: ProcLoaderCell::default(),
         });
 
         Ok(Self { inner })
     }
 
+    /// Route [`GlDisplay::get_proc_address`] through the given `loader`
+    /// instead of `glXGetProcAddress`.
+    ///
+    /// See [`crate::display::Display::set_proc_loader`] for details.
+    pub fn set_proc_loader(&self, loader: ProcLoader) -> Result<()> {
+        self.inner.proc_loader.set(loader)
+    }
+
     /// Get a reference to the initialized GLX API.
     pub fn glx(&self) -> &'static Glx {
         self.inner.glx
     }
 
+    /// The GLX version reported by `glXQueryVersion`.
+    pub fn version(&self) -> Version {
+        self.inner.version
+    }
+
+    /// The vendor of the GLX server reported by `glXQueryServerString`.
+    pub fn vendor(&self) -> Option<String> {
+        self.query_server_string(glx::VENDOR as c_int)
+    }
+
+    /// Query the server string with the given `name`, like `GLX_VERSION`,
+    /// with `glXQueryServerString`.
+    ///
+    /// [`None`] is returned when the `name` is not known to the server.
+    pub fn query_server_string(&self, name: c_int) -> Option<String> {
+        unsafe {
+            let value = self.inner.glx.QueryServerString(
+                self.inner.raw.cast(),
+                self.inner.screen as _,
+                name,
+            );
+            if value.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(value).to_string_lossy().into_owned())
+            }
+        }
+    }
+
     fn extract_display_features(
         extensions: &HashSet<&'static str>,
         version: Version,
@@ -216,6 +257,10 @@ impl GlDisplay for Display {
     }
 
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void {
+        if let Some(loader) = self.inner.proc_loader.get() {
+            return loader(addr);
+        }
+
         unsafe { self.inner.glx.GetProcAddress(addr.as_ptr() as *const _) as *const _ }
     }
 
@@ -251,6 +296,8 @@ pub(crate) struct DisplayInner {
     pub(crate) features: DisplayFeatures,
     /// Client GLX extensions.
     pub(crate) client_extensions: HashSet<&'static str>,
+    /// The loader overriding `glXGetProcAddress`.
+    pub(crate) proc_loader: ProcLoaderCell,
 }
 
 impl fmt::Debug for DisplayInner {
@@ -14,13 +14,15 @@ use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, GlSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface,
-    SurfaceAttributes, SurfaceType, SurfaceTypeTrait, SwapInterval, WindowSurface,
+    AsRawSurface, GlFunctions, GlSurface, NativePixmap, PbufferSurface, PixmapSurface, RawSurface,
+    ReadPixelsFormat, Rect, SurfaceAttributes, SurfaceOrigin, SurfaceType, SurfaceTypeTrait,
+    SwapInterval, WindowSurface,
 };
 
 use super::config::Config;
 use super::context::PossiblyCurrentContext;
 use super::display::Display;
+use super::GlxExtra;
 
 /// Hint for the attributes array.
 const ATTR_SIZE_HINT: usize = 8;
@@ -163,6 +165,17 @@ pub struct Surface<T: SurfaceTypeTrait> {
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
 impl<T: SurfaceTypeTrait> Surface<T> {
+    /// Query the config of the live drawable with `GLX_FBCONFIG_ID`.
+    ///
+    /// Unlike [`GetGlConfig::config`], which returns the config the surface
+    /// was created with, the config is resolved by the server, so it could be
+    /// used to check the attributes actually granted to the drawable, like the
+    /// number of samples.
+    pub fn query_config(&self) -> Result<Config> {
+        let config_id = unsafe { self.raw_attribute(glx::FBCONFIG_ID as c_int) };
+        self.display.config_from_id(config_id as c_int).ok_or_else(|| ErrorKind::BadConfig.into())
+    }
+
     /// # Safety
     ///
     /// The caller must ensure that the attribute could be present.
@@ -182,6 +195,149 @@ impl<T: SurfaceTypeTrait> Surface<T> {
     }
 }
 
+impl Surface<WindowSurface> {
+    /// The vertical retrace counter with `GLX_SGI_video_sync`.
+    ///
+    /// The surface must be current with the `context`.
+    ///
+    /// This function returns [`Err`] if `GLX_SGI_video_sync` is not supported.
+    pub fn video_sync_count(&self, context: &PossiblyCurrentContext) -> Result<u64> {
+        let extra = self.video_sync_extra(context)?;
+        let mut count = 0;
+        match unsafe { extra.GetVideoSyncSGI(&mut count) } {
+            0 => Ok(count as u64),
+            _ => Err(ErrorKind::BadContext.into()),
+        }
+    }
+
+    /// Block until the vertical retrace counter modulo `divisor` is equal to
+    /// `remainder`, returning the counter, with `GLX_SGI_video_sync`.
+    ///
+    /// The surface must be current with the `context`.
+    ///
+    /// This function returns [`Err`] if `GLX_SGI_video_sync` is not supported
+    /// or `remainder` is not less than `divisor`.
+    pub fn wait_for_video_sync(
+        &self,
+        context: &PossiblyCurrentContext,
+        divisor: NonZeroU32,
+        remainder: u32,
+    ) -> Result<u64> {
+        let extra = self.video_sync_extra(context)?;
+        if remainder >= divisor.get() {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        let mut count = 0;
+        match unsafe { extra.WaitVideoSyncSGI(divisor.get() as _, remainder as _, &mut count) } {
+            0 => Ok(count as u64),
+            _ => Err(ErrorKind::BadContext.into()),
+        }
+    }
+
+    /// Query the [`SyncValues`] of the surface with `GLX_OML_sync_control`.
+    ///
+    /// This function returns [`Err`] if `GLX_OML_sync_control` is not
+    /// supported.
+    pub fn sync_values(&self) -> Result<SyncValues> {
+        let extra = self.oml_sync_extra()?;
+        let (mut ust, mut msc, mut sbc) = (0, 0, 0);
+        let result = unsafe {
+            extra.GetSyncValuesOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                &mut ust,
+                &mut msc,
+                &mut sbc,
+            )
+        };
+
+        if result == 0 {
+            Err(ErrorKind::BadSurface.into())
+        } else {
+            Ok(SyncValues { ust: ust as u64, msc: msc as u64, sbc: sbc as u64 })
+        }
+    }
+
+    /// Schedule the swap to happen at the media stream counter `target_msc`
+    /// with `glXSwapBuffersMscOML`, returning the swap buffer counter the swap
+    /// will correspond to.
+    ///
+    /// When the counter is already past `target_msc`, the swap happens at the
+    /// next retrace where the counter modulo `divisor` is equal to
+    /// `remainder`, the `divisor` of zero swaps at the next retrace instead.
+    /// See [`Self::sync_values`] for the current counters.
+    ///
+    /// This function returns [`Err`] if `GLX_OML_sync_control` is not
+    /// supported or `remainder` is not less than the non zero `divisor`.
+    pub fn swap_buffers_msc(
+        &self,
+        _context: &PossiblyCurrentContext,
+        target_msc: u64,
+        divisor: u64,
+        remainder: u64,
+    ) -> Result<u64> {
+        let extra = self.oml_sync_extra()?;
+        if divisor != 0 && remainder >= divisor {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        let mut sbc = 0;
+        super::last_glx_error(|| unsafe {
+            sbc = extra.SwapBuffersMscOML(
+                self.display.inner.raw.cast(),
+                self.raw,
+                target_msc as _,
+                divisor as _,
+                remainder as _,
+            );
+        })?;
+
+        Ok(sbc as u64)
+    }
+
+    fn video_sync_extra(&self, context: &PossiblyCurrentContext) -> Result<&'static GlxExtra> {
+        let extra = match self.display.inner.glx_extra {
+            Some(extra) if self.display.inner.client_extensions.contains("GLX_SGI_video_sync") => {
+                extra
+            },
+            _ => return Err(ErrorKind::NotSupported("GLX_SGI_video_sync is not supported").into()),
+        };
+
+        // The counter is of the current drawable.
+        if !self.is_current_draw(context) {
+            return Err(ErrorKind::BadMatch.into());
+        }
+
+        Ok(extra)
+    }
+
+    fn oml_sync_extra(&self) -> Result<&'static GlxExtra> {
+        match self.display.inner.glx_extra {
+            Some(extra)
+                if self.display.inner.client_extensions.contains("GLX_OML_sync_control") =>
+            {
+                Ok(extra)
+            },
+            _ => Err(ErrorKind::NotSupported("GLX_OML_sync_control is not supported").into()),
+        }
+    }
+}
+
+/// The counters of the surface reported by `GLX_OML_sync_control`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncValues {
+    /// The unadjusted system time of the last vertical retrace, in
+    /// microseconds.
+    pub ust: u64,
+
+    /// The media stream counter, that is the number of the vertical retraces.
+    pub msc: u64,
+
+    /// The swap buffer counter, that is the number of the completed swaps.
+    pub sbc: u64,
+}
+
 impl<T: SurfaceTypeTrait> Drop for Surface<T> {
     fn drop(&mut self) {
         let _ = super::last_glx_error(|| unsafe {
@@ -225,6 +381,10 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         self.config.is_single_buffered()
     }
 
+    fn origin(&self) -> SurfaceOrigin {
+        SurfaceOrigin::BottomLeft
+    }
+
     fn swap_buffers(&self, _context: &Self::Context) -> Result<()> {
         super::last_glx_error(|| unsafe {
             self.display.inner.glx.SwapBuffers(self.display.inner.raw.cast(), self.raw);
@@ -278,6 +438,24 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn flush(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::flush(&self.display, context)
+    }
+
+    fn finish(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::finish(&self.display, context)
+    }
+
+    fn read_pixels(
+        &self,
+        context: &Self::Context,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>> {
+        let single_buffered = self.is_single_buffered();
+        GlFunctions::read_pixels(&self.display, context, single_buffered, rect, format)
+    }
+
     fn is_current(&self, context: &Self::Context) -> bool {
         self.is_current_draw(context) && self.is_current_read(context)
     }
@@ -12,7 +12,8 @@ use windows_sys::Win32::Graphics::Gdi::{self as gdi, HDC};
 use windows_sys::Win32::Graphics::OpenGL::{self as gl, PIXELFORMATDESCRIPTOR};
 
 use crate::config::{
-    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, RawConfig,
+    Api, AsRawConfig, ColorBufferType, ConfigSurfaceTypes, ConfigTemplate, GlConfig, NativeVisual,
+    RawConfig,
 };
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
@@ -32,6 +33,11 @@ impl Display {
         &self,
         template: ConfigTemplate,
     ) -> Result<Box<dyn Iterator<Item = Config> + '_>> {
+        // No config could back the pbuffer, so it can't be required.
+        if template.config_surface_types.contains(ConfigSurfaceTypes::PBUFFER) {
+            return Err(ErrorKind::NotSupported("pbuffers are not implemented with WGL").into());
+        }
+
         let hwnd = match template.native_window {
             Some(RawWindowHandle::Win32(window_handle)) => window_handle.hwnd.get() as _,
             _ => 0,
@@ -140,6 +146,16 @@ impl Display {
                 return Err(ErrorKind::BadConfig.into());
             }
 
+            // `ChoosePixelFormat` picks the closest format, which is usually the 8 bit one,
+            // so make sure that the deep color request is honored.
+            if r_size.max(g_size).max(b_size) > 8
+                && (descriptor.cRedBits < r_size
+                    || descriptor.cGreenBits < g_size
+                    || descriptor.cBlueBits < b_size)
+            {
+                return Err(ErrorKind::BadConfig.into());
+            }
+
             let inner = Arc::new(ConfigInner {
                 display: self.clone(),
                 hdc,
@@ -148,7 +164,7 @@ impl Display {
             });
             let config = Config { inner };
 
-            Ok(Box::new(iter::once(config)))
+            Ok(Box::new(iter::once(config).filter(move |config| template.within_max_sizes(config))))
         }
     }
 
@@ -233,7 +249,7 @@ impl Display {
         }
 
         if template.config_surface_types.contains(ConfigSurfaceTypes::PIXMAP) {
-            attrs.push(wgl_extra::DRAW_TO_WINDOW_ARB as c_int);
+            attrs.push(wgl_extra::DRAW_TO_BITMAP_ARB as c_int);
             attrs.push(1);
         }
 
@@ -262,15 +278,20 @@ impl Display {
             }
             configs.set_len(num_configs as _);
 
-            Ok(Box::new(configs.into_iter().map(move |pixel_format_index| {
-                let inner = Arc::new(ConfigInner {
-                    display: self.clone(),
-                    hdc,
-                    pixel_format_index,
-                    descriptor: None,
-                });
-                Config { inner }
-            })))
+            let configs = configs
+                .into_iter()
+                .map(move |pixel_format_index| {
+                    let inner = Arc::new(ConfigInner {
+                        display: self.clone(),
+                        hdc,
+                        pixel_format_index,
+                        descriptor: None,
+                    });
+                    Config { inner }
+                })
+                .filter(move |config| template.within_max_sizes(config));
+
+            Ok(Box::new(configs))
         }
     }
 }
@@ -397,6 +418,15 @@ impl GlConfig for Config {
         }
     }
 
+    fn num_sample_buffers(&self) -> u8 {
+        if self.inner.display.inner.features.contains(DisplayFeatures::MULTISAMPLING_PIXEL_FORMATS)
+        {
+            unsafe { self.raw_attribute(wgl_extra::SAMPLE_BUFFERS_ARB as c_int) as _ }
+        } else {
+            0
+        }
+    }
+
     fn config_surface_types(&self) -> ConfigSurfaceTypes {
         let mut flags = ConfigSurfaceTypes::empty();
         match self.inner.descriptor.as_ref() {
@@ -415,7 +445,7 @@ impl GlConfig for Config {
                     flags |= ConfigSurfaceTypes::WINDOW
                 }
                 if self.raw_attribute(wgl_extra::DRAW_TO_BITMAP_ARB as c_int) != 0 {
-                    flags |= ConfigSurfaceTypes::WINDOW
+                    flags |= ConfigSurfaceTypes::PIXMAP
                 }
             },
         }
@@ -423,6 +453,18 @@ impl GlConfig for Config {
         flags
     }
 
+    fn max_pbuffer_width(&self) -> u32 {
+        0
+    }
+
+    fn max_pbuffer_height(&self) -> u32 {
+        0
+    }
+
+    fn max_pbuffer_pixels(&self) -> u32 {
+        0
+    }
+
     fn hardware_accelerated(&self) -> bool {
         if let Some(descriptor) = self.inner.descriptor.as_ref() {
             descriptor.dwFlags & gl::PFD_GENERIC_ACCELERATED != 0
@@ -442,6 +484,10 @@ impl GlConfig for Config {
         }
     }
 
+    fn native_visual_info(&self) -> Option<NativeVisual> {
+        None
+    }
+
     fn api(&self) -> Api {
         let mut api = Api::OPENGL;
         if self.inner.display.inner.features.contains(DisplayFeatures::CREATE_ES_CONTEXT) {
@@ -14,13 +14,14 @@ use windows_sys::Win32::Graphics::Gdi::{self as gdi, HDC};
 use crate::config::GetGlConfig;
 use crate::context::{
     self, AsRawContext, ContextApi, ContextAttributes, GlProfile, RawContext, ReleaseBehavior,
-    Robustness, Version,
+    ResetNotification, Robustness, Version,
 };
+use crate::current_guard;
 use crate::display::{DisplayFeatures, GetGlDisplay};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
-use crate::surface::SurfaceTypeTrait;
+use crate::surface::{RawSurface, SurfaceTypeTrait};
 
 use super::config::Config;
 use super::display::Display;
@@ -32,6 +33,8 @@ impl Display {
         config: &Config,
         context_attributes: &ContextAttributes,
     ) -> Result<NotCurrentContext> {
+        context_attributes.validate()?;
+
         let hdc = match context_attributes.raw_window_handle.as_ref() {
             handle @ Some(RawWindowHandle::Win32(window)) => unsafe {
                 let _ = config.apply_on_native_window(handle.unwrap());
@@ -45,8 +48,17 @@ impl Display {
             _ => std::ptr::null(),
         };
 
-        let context = if self.inner.client_extensions.contains("WGL_ARB_create_context") {
-            self.create_context_arb(hdc, share_ctx, context_attributes)?
+        let (context, version) = if self.inner.client_extensions.contains("WGL_ARB_create_context")
+        {
+            let result = context_attributes
+                .with_highest_core_version(|attrs| self.create_context_arb(hdc, share_ctx, attrs));
+            match result {
+                Some((context, version)) => (context, Some(version)),
+                None => (
+                    self.create_context_arb(hdc, share_ctx, context_attributes)?,
+                    context_attributes.api.and_then(|api| api.version()),
+                ),
+            }
         } else {
             unsafe {
                 let raw = wgl::CreateContext(hdc as *const _);
@@ -59,16 +71,52 @@ impl Display {
                     return Err(IoError::last_os_error().into());
                 }
 
-                WglContext(raw)
+                (WglContext(raw), None)
             }
         };
 
         let config = config.clone();
         let is_gles = matches!(context_attributes.api, Some(ContextApi::Gles(_)));
-        let inner = ContextInner { display: self.clone(), config, raw: context, is_gles };
+        let inner = ContextInner {
+            display: self.clone(),
+            config,
+            raw: context,
+            is_gles,
+            version,
+            owned: true,
+        };
         Ok(NotCurrentContext { inner })
     }
 
+    /// Wrap the `HGLRC` created outside of glutin.
+    ///
+    /// The context is not destroyed when the returned value is dropped, the
+    /// caller remains the owner of it.
+    ///
+    /// # Safety
+    ///
+    /// The `raw` must be a valid `HGLRC` created with the pixel format of the
+    /// `config`, and it must outlive the returned context.
+    pub unsafe fn context_from_raw(
+        &self,
+        config: &Config,
+        raw: HGLRC,
+    ) -> Result<PossiblyCurrentContext> {
+        if raw.is_null() {
+            return Err(ErrorKind::BadContext.into());
+        }
+
+        let inner = ContextInner {
+            display: self.clone(),
+            config: config.clone(),
+            raw: WglContext(raw),
+            is_gles: false,
+            version: None,
+            owned: false,
+        };
+        Ok(PossiblyCurrentContext { inner, _nosendsync: PhantomData })
+    }
+
     fn create_context_arb(
         &self,
         hdc: HDC,
@@ -133,14 +181,7 @@ impl Display {
         if self.inner.features.contains(DisplayFeatures::CONTEXT_ROBUSTNESS) {
             match context_attributes.robustness {
                 Robustness::NotRobust => (),
-                Robustness::RobustNoResetNotification => {
-                    attrs.push(wgl_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
-                    attrs.push(wgl_extra::NO_RESET_NOTIFICATION_ARB as c_int);
-                    flags |= wgl_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
-                },
-                Robustness::RobustLoseContextOnReset => {
-                    attrs.push(wgl_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
-                    attrs.push(wgl_extra::LOSE_CONTEXT_ON_RESET_ARB as c_int);
+                Robustness::RobustNoResetNotification | Robustness::RobustLoseContextOnReset => {
                     flags |= wgl_extra::CONTEXT_ROBUST_ACCESS_BIT_ARB as c_int;
                 },
                 Robustness::NoError => {
@@ -156,7 +197,18 @@ impl Display {
                     requested_no_error = true;
                 },
             }
-        } else if context_attributes.robustness != Robustness::NotRobust {
+
+            if let Some(reset_notification) = context_attributes.reset_notification() {
+                let strategy = match reset_notification {
+                    ResetNotification::NoResetNotification => wgl_extra::NO_RESET_NOTIFICATION_ARB,
+                    ResetNotification::LoseContextOnReset => wgl_extra::LOSE_CONTEXT_ON_RESET_ARB,
+                };
+                attrs.push(wgl_extra::CONTEXT_RESET_NOTIFICATION_STRATEGY_ARB as c_int);
+                attrs.push(strategy as c_int);
+            }
+        } else if context_attributes.robustness != Robustness::NotRobust
+            || context_attributes.reset_notification.is_some()
+        {
             return Err(ErrorKind::NotSupported(
                 "WGL_ARB_create_context_robustness is not supported",
             )
@@ -168,6 +220,13 @@ impl Display {
             flags |= wgl_extra::CONTEXT_DEBUG_BIT_ARB as c_int;
         }
 
+        // Forward compatible flag, which only applies to desktop OpenGL.
+        if context_attributes.forward_compatible
+            && !matches!(context_attributes.api, Some(ContextApi::Gles(_)))
+        {
+            flags |= wgl_extra::CONTEXT_FORWARD_COMPATIBLE_BIT_ARB as c_int;
+        }
+
         if flags != 0 {
             attrs.push(wgl_extra::CONTEXT_FLAGS_ARB as c_int);
             attrs.push(flags as c_int);
@@ -238,10 +297,10 @@ impl NotCurrentGlContext for NotCurrentContext {
         Ok(PossiblyCurrentContext { inner: self.inner, _nosendsync: PhantomData })
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<Self::PossiblyCurrentContext> {
         Err(self.inner.make_current_draw_read(surface_draw, surface_read).into())
     }
@@ -294,6 +353,7 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
                 if wgl::MakeCurrent(hdc, std::ptr::null()) == 0 {
                     return Err(IoError::last_os_error().into());
                 }
+                current_guard::mark_not_current(0);
             }
 
             Ok(NotCurrentContext::new(self.inner))
@@ -304,14 +364,23 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
         unsafe { wgl::GetCurrentContext() == *self.inner.raw }
     }
 
+    fn query_current_surface(&self) -> Option<(RawSurface, RawSurface)> {
+        if !self.is_current() {
+            return None;
+        }
+
+        let hwnd = unsafe { gdi::WindowFromDC(wgl::GetCurrentDC() as _) };
+        Some((RawSurface::Wgl(hwnd as _), RawSurface::Wgl(hwnd as _)))
+    }
+
     fn make_current<T: SurfaceTypeTrait>(&self, surface: &Self::Surface<T>) -> Result<()> {
         self.inner.make_current(surface)
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<()> {
         Err(self.inner.make_current_draw_read(surface_draw, surface_read).into())
     }
@@ -352,6 +421,10 @@ struct ContextInner {
     config: Config,
     raw: WglContext,
     is_gles: bool,
+    /// The version requested when creating the context, if known.
+    version: Option<Version>,
+    /// Whether the context was created by glutin and should be destroyed.
+    owned: bool,
 }
 
 impl fmt::Debug for ContextInner {
@@ -359,6 +432,7 @@ impl fmt::Debug for ContextInner {
         f.debug_struct("Context")
             .field("config", &self.config.inner.pixel_format_index)
             .field("raw", &self.raw)
+            .field("owned", &self.owned)
             .finish()
     }
 }
@@ -377,19 +451,21 @@ impl Deref for WglContext {
 unsafe impl Send for WglContext {}
 
 impl ContextInner {
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         _surface_draw: &Surface<T>,
-        _surface_read: &Surface<T>,
+        _surface_read: &Surface<U>,
     ) -> ErrorKind {
         ErrorKind::NotSupported("make_current_draw_read is not supported by WGL")
     }
 
     fn make_current<T: SurfaceTypeTrait>(&self, surface: &Surface<T>) -> Result<()> {
+        current_guard::check_make_current(*self.raw as usize);
         unsafe {
             if wgl::MakeCurrent(surface.hdc as _, self.raw.cast()) == 0 {
                 Err(IoError::last_os_error().into())
             } else {
+                current_guard::mark_current(*self.raw as usize, 0);
                 Ok(())
             }
         }
@@ -397,17 +473,23 @@ impl ContextInner {
 
     fn context_api(&self) -> ContextApi {
         if self.is_gles {
-            ContextApi::Gles(None)
+            ContextApi::Gles(self.version)
         } else {
-            ContextApi::OpenGl(None)
+            ContextApi::OpenGl(self.version)
         }
     }
 }
 
 impl Drop for ContextInner {
     fn drop(&mut self) {
-        unsafe {
-            wgl::DeleteContext(*self.raw);
+        if self.owned {
+            unsafe {
+                wgl::DeleteContext(*self.raw);
+            }
         }
+
+        // The adopted context could be destroyed by its owner right after, and the
+        // handle reused by the new one.
+        current_guard::forget(*self.raw as usize);
     }
 }
@@ -1,20 +1,23 @@
 //! WGL display initialization and extension loading.
 
 use std::borrow::Cow;
-use std::collections::HashSet;
-use std::ffi::{self, CStr, OsStr};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{self, CStr, CString, OsStr};
 use std::os::windows::ffi::OsStrExt;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{env, fmt};
 
 use glutin_wgl_sys::wgl;
 use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
-use windows_sys::Win32::Foundation::HMODULE;
-use windows_sys::Win32::Graphics::Gdi::HDC;
+use windows_sys::Win32::Foundation::{HMODULE, HWND};
+use windows_sys::Win32::Graphics::Gdi::{self as gdi, HDC};
+use windows_sys::Win32::Graphics::OpenGL as gl;
 use windows_sys::Win32::System::LibraryLoader as dll_loader;
 
 use crate::config::ConfigTemplate;
-use crate::display::{AsRawDisplay, DisplayFeatures, GetDisplayExtensions, RawDisplay};
+use crate::display::{
+    AsRawDisplay, DisplayFeatures, GetDisplayExtensions, ProcLoader, ProcLoaderCell, RawDisplay,
+};
 use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
@@ -47,7 +50,8 @@ impl Display {
     /// # Safety
     ///
     /// The `native_window` must point to the valid platform window and have
-    /// valid `hinstance`.
+    /// valid `hinstance`. The display holds the device context of that window,
+    /// exposed as [`RawDisplay::Wgl`], so the window must outlive the display.
     pub unsafe fn new(
         display: RawDisplayHandle,
         native_window: Option<RawWindowHandle>,
@@ -66,26 +70,45 @@ impl Display {
         }
 
         // In case native window was provided init extra functions.
-        let (wgl_extra, client_extensions) =
+        let (wgl_extra, client_extensions, hwnd) =
             if let Some(RawWindowHandle::Win32(window)) = native_window {
                 unsafe {
                     let (wgl_extra, client_extensions) = super::load_extra_functions(
                         window.hinstance.unwrap().get() as _,
                         window.hwnd.get() as _,
                     )?;
-                    (Some(wgl_extra), client_extensions)
+                    (Some(wgl_extra), client_extensions, window.hwnd.get() as HWND)
                 }
             } else {
-                (None, HashSet::new())
+                (None, HashSet::new(), 0)
             };
 
+        let hdc = if hwnd != 0 { unsafe { gdi::GetDC(hwnd) } } else { 0 };
+
         let features = Self::extract_display_features(&client_extensions);
 
-        let inner = Arc::new(DisplayInner { lib_opengl32, wgl_extra, features, client_extensions });
+        let inner = Arc::new(DisplayInner {
+            lib_opengl32,
+            hwnd,
+            hdc,
+            wgl_extra,
+            features,
+            client_extensions,
+            proc_addresses: Mutex::new(HashMap::new()),
+            proc_loader: ProcLoaderCell::default(),
+        });
 
         Ok(Display { inner })
     }
 
+    /// Route [`GlDisplay::get_proc_address`] through the given `loader`
+    /// instead of `wglGetProcAddress`.
+    ///
+    /// See [`crate::display::Display::set_proc_loader`] for details.
+    pub fn set_proc_loader(&self, loader: ProcLoader) -> Result<()> {
+        self.inner.proc_loader.set(loader)
+    }
+
     fn extract_display_features(extensions: &HashSet<&'static str>) -> DisplayFeatures {
         let mut features = DisplayFeatures::empty();
 
@@ -180,16 +203,46 @@ impl GlDisplay for Display {
     }
 
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void {
-        unsafe {
-            let addr = addr.as_ptr();
-            let fn_ptr = wgl::GetProcAddress(addr);
-            if !fn_ptr.is_null() {
+        if let Some(loader) = self.inner.proc_loader.get() {
+            return loader(addr);
+        }
+
+        // The `wglGetProcAddress` results depend on the pixel format of the current
+        // context, since the configs could be served by the different drivers.
+        let pixel_format = unsafe {
+            let hdc = wgl::GetCurrentDC();
+            if hdc.is_null() {
+                0
+            } else {
+                gl::GetPixelFormat(hdc as _)
+            }
+        };
+        let key = (pixel_format, addr.to_owned());
+
+        let mut proc_addresses = self.inner.proc_addresses.lock().unwrap();
+        if let Some(&fn_ptr) = proc_addresses.get(&key) {
+            return fn_ptr as *const _;
+        }
+
+        let fn_ptr: *const ffi::c_void = unsafe {
+            // Some implementations return the `1`, `2`, `3` or `-1` instead of null for
+            // the functions exported by `opengl32.dll` only, like the OpenGL 1.1 ones.
+            let fn_ptr = wgl::GetProcAddress(addr.as_ptr());
+            if !matches!(fn_ptr as isize, -1..=3) {
                 fn_ptr.cast()
             } else {
-                dll_loader::GetProcAddress(self.inner.lib_opengl32, addr.cast())
+                dll_loader::GetProcAddress(self.inner.lib_opengl32, addr.as_ptr().cast())
                     .map_or(std::ptr::null(), |fn_ptr| fn_ptr as *const _)
             }
+        };
+
+        // Only cache the found functions, since the lookup could fail due to the
+        // lack of the current context.
+        if !fn_ptr.is_null() {
+            proc_addresses.insert(key, fn_ptr as usize);
         }
+
+        fn_ptr
     }
 
     fn version_string(&self) -> String {
@@ -209,7 +262,7 @@ impl GetDisplayExtensions for Display {
 
 impl AsRawDisplay for Display {
     fn raw_display(&self) -> RawDisplay {
-        RawDisplay::Wgl
+        RawDisplay::Wgl(self.inner.hdc as _)
     }
 }
 
@@ -219,17 +272,45 @@ pub(crate) struct DisplayInner {
     /// Client WGL extensions.
     pub(crate) lib_opengl32: HMODULE,
 
+    /// The window used to create the display, if any.
+    pub(crate) hwnd: HWND,
+
+    /// The device context of the `hwnd`, if any, owned by the display.
+    ///
+    /// The `hwnd` must outlive the display, since its handle could be reused
+    /// by the other window once it's destroyed, so there's no way to tell
+    /// whether the device context is still valid.
+    pub(crate) hdc: HDC,
+
     /// Extra functions used by the impl.
     pub(crate) wgl_extra: Option<&'static WglExtra>,
 
     pub(crate) features: DisplayFeatures,
 
     pub(crate) client_extensions: HashSet<&'static str>,
+
+    /// Addresses resolved by `get_proc_address`, keyed by the pixel format of
+    /// the context current during the lookup.
+    pub(crate) proc_addresses: Mutex<HashMap<(i32, CString), usize>>,
+
+    /// The loader overriding `wglGetProcAddress`.
+    pub(crate) proc_loader: ProcLoaderCell,
+}
+
+impl Drop for DisplayInner {
+    fn drop(&mut self) {
+        if self.hdc != 0 {
+            unsafe {
+                gdi::ReleaseDC(self.hwnd, self.hdc);
+            }
+        }
+    }
 }
 
 impl fmt::Debug for DisplayInner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Display")
+            .field("hdc", &self.hdc)
             .field("features", &self.features)
             .field("extensions", &self.client_extensions)
             .finish()
@@ -0,0 +1,183 @@
+//! Sharing the Direct3D resources with OpenGL through `WGL_NV_DX_interop2`.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::io::Error as IoError;
+
+use glutin_wgl_sys::wgl_extra;
+
+use crate::error::{ErrorKind, Result};
+
+use super::display::Display;
+use super::WglExtra;
+
+/// The access the OpenGL has to the registered Direct3D object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DxAccess {
+    /// The OpenGL only reads the object.
+    ReadOnly,
+
+    /// The OpenGL reads and writes the object.
+    ReadWrite,
+
+    /// The OpenGL overwrites the whole object, so its contents are undefined
+    /// when locked.
+    WriteDiscard,
+}
+
+impl DxAccess {
+    fn raw(self) -> u32 {
+        match self {
+            Self::ReadOnly => wgl_extra::ACCESS_READ_ONLY_NV,
+            Self::ReadWrite => wgl_extra::ACCESS_READ_WRITE_NV,
+            Self::WriteDiscard => wgl_extra::ACCESS_WRITE_DISCARD_NV,
+        }
+    }
+}
+
+/// The Direct3D device opened for the interop with `wglDXOpenDeviceNV`.
+///
+/// The device is closed on drop.
+pub struct WglDxInterop {
+    extra: &'static WglExtra,
+    device: *const c_void,
+}
+
+impl WglDxInterop {
+    /// Open the Direct3D device for sharing its resources with the OpenGL
+    /// contexts of the `display`.
+    ///
+    /// This function returns [`Err`] if `WGL_NV_DX_interop2` is not supported.
+    ///
+    /// # Safety
+    ///
+    /// The `d3d_device` must be a valid pointer to `ID3D11Device`,
+    /// `ID3D10Device` or `IDirect3DDevice9Ex`, and it must outlive the
+    /// returned value.
+    pub unsafe fn open(display: &Display, d3d_device: *mut c_void) -> Result<Self> {
+        let extra = match display.inner.wgl_extra {
+            Some(extra) if display.inner.client_extensions.contains("WGL_NV_DX_interop2") => extra,
+            _ => return Err(ErrorKind::NotSupported("WGL_NV_DX_interop2 is not supported").into()),
+        };
+
+        let device = unsafe { extra.DXOpenDeviceNV(d3d_device as _) };
+        if device.is_null() {
+            return Err(IoError::last_os_error().into());
+        }
+
+        Ok(Self { extra, device: device as _ })
+    }
+
+    /// Register the Direct3D resource, so it backs the OpenGL object `name` of
+    /// the given `target`, like the texture created with `glGenTextures` and
+    /// `GL_TEXTURE_2D`, or the renderbuffer with `GL_RENDERBUFFER`.
+    ///
+    /// The object is registered with the context current on the calling
+    /// thread, and could be used by OpenGL only while locked with
+    /// [`Self::lock`].
+    ///
+    /// # Safety
+    ///
+    /// The `d3d_resource` must be a valid pointer to the resource of the
+    /// opened device, and `name` must be the unused object of the current
+    /// context.
+    pub unsafe fn register_object(
+        &self,
+        d3d_resource: *mut c_void,
+        name: u32,
+        target: u32,
+        access: DxAccess,
+    ) -> Result<DxObject<'_>> {
+        let raw = unsafe {
+            self.extra.DXRegisterObjectNV(
+                self.device as _,
+                d3d_resource as _,
+                name as _,
+                target as _,
+                access.raw() as _,
+            )
+        };
+
+        if raw.is_null() {
+            Err(IoError::last_os_error().into())
+        } else {
+            Ok(DxObject { interop: self, raw: raw as _ })
+        }
+    }
+
+    /// Lock the `objects` for the OpenGL access, during which Direct3D must
+    /// not use them.
+    pub fn lock(&self, objects: &[&DxObject<'_>]) -> Result<()> {
+        let mut raw: Vec<_> = objects.iter().map(|object| object.raw).collect();
+        if unsafe {
+            self.extra.DXLockObjectsNV(self.device as _, raw.len() as _, raw.as_mut_ptr() as _)
+        } == 0
+        {
+            Err(IoError::last_os_error().into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Unlock the `objects` locked with [`Self::lock`], handing them back to
+    /// Direct3D.
+    pub fn unlock(&self, objects: &[&DxObject<'_>]) -> Result<()> {
+        let mut raw: Vec<_> = objects.iter().map(|object| object.raw).collect();
+        if unsafe {
+            self.extra.DXUnlockObjectsNV(self.device as _, raw.len() as _, raw.as_mut_ptr() as _)
+        } == 0
+        {
+            Err(IoError::last_os_error().into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Get the raw handle returned by `wglDXOpenDeviceNV`.
+    pub fn raw_device(&self) -> *const c_void {
+        self.device
+    }
+}
+
+impl Drop for WglDxInterop {
+    fn drop(&mut self) {
+        unsafe {
+            self.extra.DXCloseDeviceNV(self.device as _);
+        }
+    }
+}
+
+impl fmt::Debug for WglDxInterop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WglDxInterop").field("device", &self.device).finish()
+    }
+}
+
+/// The Direct3D resource registered with [`WglDxInterop::register_object`].
+///
+/// The object is unregistered on drop, which must happen while it's unlocked.
+pub struct DxObject<'a> {
+    interop: &'a WglDxInterop,
+    raw: *const c_void,
+}
+
+impl DxObject<'_> {
+    /// Get the raw handle returned by `wglDXRegisterObjectNV`.
+    pub fn raw_object(&self) -> *const c_void {
+        self.raw
+    }
+}
+
+impl Drop for DxObject<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.interop.extra.DXUnregisterObjectNV(self.interop.device as _, self.raw as _);
+        }
+    }
+}
+
+impl fmt::Debug for DxObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DxObject").field("raw", &self.raw).finish()
+    }
+}
@@ -9,7 +9,7 @@ use std::os::windows::ffi::OsStrExt;
 
 use glutin_wgl_sys::{wgl, wgl_extra};
 use once_cell::sync::OnceCell;
-use windows_sys::Win32::Foundation::{HMODULE, HWND};
+use windows_sys::Win32::Foundation::{self as foundation, HMODULE, HWND};
 use windows_sys::Win32::Graphics::{Gdi as gdi, OpenGL as gl};
 use windows_sys::Win32::UI::WindowsAndMessaging::{self as wm, WINDOWPLACEMENT, WNDCLASSEXW};
 
@@ -18,10 +18,20 @@ use crate::error::{Error, ErrorKind, Result};
 pub mod config;
 pub mod context;
 pub mod display;
+pub mod dx_interop;
 pub mod surface;
 
 pub(crate) static WGL_EXTRA: OnceCell<WglExtra> = OnceCell::new();
 
+/// The `ERROR_INVALID_VERSION_ARB` of `WGL_ARB_create_context`.
+const ERROR_INVALID_VERSION_ARB: u32 = 0x2095;
+
+/// The `ERROR_INVALID_PROFILE_ARB` of `WGL_ARB_create_context_profile`.
+const ERROR_INVALID_PROFILE_ARB: u32 = 0x2096;
+
+/// The facility bits some drivers set on the `WGL_ARB_create_context` errors.
+const ERROR_ARB_FACILITY: u32 = 0xc007_0000;
+
 pub(crate) struct WglExtra(wgl_extra::Wgl);
 
 unsafe impl Send for WglExtra {}
@@ -144,6 +154,23 @@ unsafe fn load_extra_functions(
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
         let raw = error.raw_os_error().map(|code| code as i64);
-        Error::new(raw, Some(error.to_string()), ErrorKind::Misc)
+        let code = error.raw_os_error().map(|code| match code as u32 {
+            code if code & ERROR_ARB_FACILITY == ERROR_ARB_FACILITY => code & !ERROR_ARB_FACILITY,
+            code => code,
+        });
+        let kind = match code {
+            Some(foundation::ERROR_NOT_ENOUGH_MEMORY | foundation::ERROR_OUTOFMEMORY) => {
+                ErrorKind::OutOfMemory
+            },
+            Some(foundation::ERROR_INVALID_PARAMETER) => ErrorKind::BadParameter,
+            Some(ERROR_INVALID_VERSION_ARB | ERROR_INVALID_PROFILE_ARB) => ErrorKind::BadMatch,
+            Some(foundation::ERROR_INVALID_PIXEL_FORMAT) => ErrorKind::BadConfig,
+            Some(foundation::ERROR_INVALID_WINDOW_HANDLE) => ErrorKind::BadNativeWindow,
+            Some(foundation::ERROR_INVALID_HANDLE | foundation::ERROR_DC_NOT_FOUND) => {
+                ErrorKind::BadSurface
+            },
+            _ => ErrorKind::Misc,
+        };
+        Error::new(raw, Some(error.to_string()), kind)
     }
 }
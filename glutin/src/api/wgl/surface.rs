@@ -9,6 +9,7 @@ use raw_window_handle::RawWindowHandle;
 use windows_sys::Win32::Foundation::{HWND, RECT};
 use windows_sys::Win32::Graphics::Gdi::HDC;
 use windows_sys::Win32::Graphics::{Gdi as gdi, OpenGL as gl};
+use windows_sys::Win32::UI::ColorSystem::SetDeviceGammaRamp;
 use windows_sys::Win32::UI::WindowsAndMessaging::GetClientRect;
 
 use crate::config::GetGlConfig;
@@ -17,8 +18,9 @@ use crate::error::{ErrorKind, Result};
 use crate::prelude::*;
 use crate::private::Sealed;
 use crate::surface::{
-    AsRawSurface, GlSurface, PbufferSurface, PixmapSurface, RawSurface, SurfaceAttributes,
-    SurfaceTypeTrait, SwapInterval, WindowSurface,
+    AsRawSurface, GlFunctions, GlSurface, PbufferSurface, PixmapSurface, RawSurface,
+    ReadPixelsFormat, Rect, SurfaceAttributes, SurfaceOrigin, SurfaceTypeTrait, SwapInterval,
+    WindowSurface,
 };
 
 use super::config::Config;
@@ -61,11 +63,55 @@ impl Display {
 
         let hdc = unsafe { gdi::GetDC(hwnd) };
 
-        let surface =
-            Surface { display: self.clone(), config: config.clone(), hwnd, hdc, _ty: PhantomData };
+        let surface = Surface {
+            display: self.clone(),
+            config: config.clone(),
+            hwnd,
+            hdc,
+            owned: true,
+            _ty: PhantomData,
+        };
 
         Ok(surface)
     }
+
+    /// Wrap the window `HDC` obtained outside of glutin, checking it first.
+    ///
+    /// Unlike pointers, the `HDC` is a handle validated by the system, thus
+    /// [`ErrorKind::BadNativeWindow`] is returned when it isn't a device
+    /// context of a window, and [`ErrorKind::BadMatch`] when its pixel format
+    /// doesn't match the `config`.
+    ///
+    /// The `hdc` is not released when the returned value is dropped, the
+    /// caller remains the owner of it. Once the caller releases it, the
+    /// operations on the surface fail.
+    pub fn try_window_surface_from_raw(
+        &self,
+        config: &Config,
+        hdc: HDC,
+    ) -> Result<Surface<WindowSurface>> {
+        if unsafe { gdi::GetObjectType(hdc as _) } as u32 != gdi::OBJ_DC as u32 {
+            return Err(ErrorKind::BadNativeWindow.into());
+        }
+
+        let hwnd = unsafe { gdi::WindowFromDC(hdc) };
+        if hwnd == 0 {
+            return Err(ErrorKind::BadNativeWindow.into());
+        }
+
+        if unsafe { gl::GetPixelFormat(hdc) } != config.inner.pixel_format_index {
+            return Err(ErrorKind::BadMatch.into());
+        }
+
+        Ok(Surface {
+            display: self.clone(),
+            config: config.clone(),
+            hwnd,
+            hdc,
+            owned: false,
+            _ty: PhantomData,
+        })
+    }
 }
 
 /// A Wrapper around `HWND`.
@@ -74,16 +120,75 @@ pub struct Surface<T: SurfaceTypeTrait> {
     config: Config,
     pub(crate) hwnd: HWND,
     pub(crate) hdc: HDC,
+    /// Whether the `hdc` was obtained by glutin and should be released.
+    owned: bool,
     _ty: PhantomData<T>,
 }
 
 // Impl only `Send` for Surface.
 unsafe impl<T: SurfaceTypeTrait> Send for Surface<T> {}
 
+impl<T: SurfaceTypeTrait> Surface<T> {
+    /// The width and the height of the client area of the surface's window,
+    /// read at once with `GetClientRect`.
+    ///
+    /// Since WGL has no drawable of its own, this is the size the surface
+    /// covers, which [`GlSurface::width`] and [`GlSurface::height`] also
+    /// report.
+    ///
+    /// [`None`] is returned when the window is gone.
+    pub fn client_size(&self) -> Option<(u32, u32)> {
+        let mut rect: RECT = unsafe { mem::zeroed() };
+        if unsafe { GetClientRect(self.hwnd, &mut rect) } == false.into() {
+            None
+        } else {
+            Some(((rect.right - rect.left) as u32, (rect.bottom - rect.top) as u32))
+        }
+    }
+
+    /// The `HDC` of the window the surface renders into, while
+    /// [`AsRawSurface::raw_surface`] returns its `HWND`.
+    ///
+    /// The device context obtained by glutin is owned by the surface, so it
+    /// must not be released. The one passed to
+    /// [`Display::try_window_surface_from_raw`] stays owned by the caller.
+    pub fn raw_device_context(&self) -> *const std::ffi::c_void {
+        self.hdc as _
+    }
+
+    /// Set the gamma ramp of the display device the surface's window is on
+    /// with `SetDeviceGammaRamp`, e.g. to apply the calibration.
+    ///
+    /// Every channel maps the 256 input intensities, from black to white, to
+    /// the output ones, where `0` is the darkest and `u16::MAX` is the
+    /// brightest. The identity ramp is `i * 257` for the `i`th entry.
+    ///
+    /// The ramp affects the whole display device, not only the window, and
+    /// Windows may reject the ramps deviating too much from the identity one,
+    /// in which case the error is returned.
+    pub fn set_gamma_ramp(
+        &self,
+        red: &[u16; 256],
+        green: &[u16; 256],
+        blue: &[u16; 256],
+    ) -> Result<()> {
+        let ramp = [*red, *green, *blue];
+        unsafe {
+            if SetDeviceGammaRamp(self.hdc, ramp.as_ptr().cast()) == 0 {
+                Err(IoError::last_os_error().into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
 impl<T: SurfaceTypeTrait> Drop for Surface<T> {
     fn drop(&mut self) {
-        unsafe {
-            gdi::ReleaseDC(self.hwnd, self.hdc);
+        if self.owned {
+            unsafe {
+                gdi::ReleaseDC(self.hwnd, self.hdc);
+            }
         }
     }
 }
@@ -97,27 +202,21 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 
     fn width(&self) -> Option<u32> {
-        let mut rect: RECT = unsafe { mem::zeroed() };
-        if unsafe { GetClientRect(self.hwnd, &mut rect) } == false.into() {
-            None
-        } else {
-            Some((rect.right - rect.left) as u32)
-        }
+        self.client_size().map(|(width, _)| width)
     }
 
     fn height(&self) -> Option<u32> {
-        let mut rect: RECT = unsafe { mem::zeroed() };
-        if unsafe { GetClientRect(self.hwnd, &mut rect) } == false.into() {
-            None
-        } else {
-            Some((rect.bottom - rect.top) as u32)
-        }
+        self.client_size().map(|(_, height)| height)
     }
 
     fn is_single_buffered(&self) -> bool {
         self.config.is_single_buffered()
     }
 
+    fn origin(&self) -> SurfaceOrigin {
+        SurfaceOrigin::BottomLeft
+    }
+
     fn swap_buffers(&self, _context: &Self::Context) -> Result<()> {
         unsafe {
             if gl::SwapBuffers(self.hdc) == 0 {
@@ -152,6 +251,24 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn flush(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::flush(&self.display, context)
+    }
+
+    fn finish(&self, context: &Self::Context) -> Result<()> {
+        GlFunctions::finish(&self.display, context)
+    }
+
+    fn read_pixels(
+        &self,
+        context: &Self::Context,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>> {
+        let single_buffered = self.is_single_buffered();
+        GlFunctions::read_pixels(&self.display, context, single_buffered, rect, format)
+    }
+
     fn is_current(&self, context: &Self::Context) -> bool {
         context.is_current()
     }
@@ -2,6 +2,7 @@
 #![allow(unreachable_patterns)]
 
 use std::num::NonZeroU32;
+use std::ops::RangeInclusive;
 
 use bitflags::bitflags;
 use raw_window_handle::RawWindowHandle;
@@ -23,14 +24,42 @@ use crate::api::wgl::config::Config as WglConfig;
 
 /// The trait to group all common config option.
 pub trait GlConfig: Sealed {
-    /// The type of the underlying color buffer.
+    /// The type of the underlying color buffer with the sizes of its
+    /// components, while the size of the alpha is reported separately by
+    /// [`Self::alpha_size`].
     ///
     /// `None` is returned when the format can not be identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL / CGL:** always [`ColorBufferType::Rgb`].
+    /// - **CGL:** the component sizes are derived from the total color size.
     fn color_buffer_type(&self) -> Option<ColorBufferType>;
 
     /// Whether the config uses floating pixels.
     fn float_pixels(&self) -> bool;
 
+    /// The type of the color components of the config.
+    ///
+    /// Unlike the template passed to [`GlDisplay::find_configs`], this is
+    /// queried from the config itself, so the [`ComponentType::Fixed`]
+    /// buffers clamp the written colors to `[0, 1]`, while the
+    /// [`ComponentType::Float`] ones don't.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** queried with `EGL_COLOR_COMPONENT_TYPE_EXT`, always
+    ///   [`ComponentType::Fixed`] without `EGL_EXT_pixel_format_float`.
+    ///
+    /// [`GlDisplay::find_configs`]: crate::display::GlDisplay::find_configs
+    fn color_component_type(&self) -> ComponentType {
+        if self.float_pixels() {
+            ComponentType::Float
+        } else {
+            ComponentType::Fixed
+        }
+    }
+
     /// The size of the alpha.
     fn alpha_size(&self) -> u8;
 
@@ -45,6 +74,13 @@ pub trait GlConfig: Sealed {
     /// Zero would mean that there're no samples.
     fn num_samples(&self) -> u8;
 
+    /// The number of multisample buffers.
+    ///
+    /// Unlike [`Self::num_samples`], which some drivers report as `1` for the
+    /// single sampled configs, this tells whether the multisampling is
+    /// actually active, since it's non zero only in that case.
+    fn num_sample_buffers(&self) -> u8;
+
     /// Whether the config supports creating srgb capable [`Surface`].
     ///
     /// [`Surface`]: crate::surface::Surface
@@ -56,17 +92,77 @@ pub trait GlConfig: Sealed {
     /// identified, in that case transparent window could still work.
     fn supports_transparency(&self) -> Option<bool>;
 
+    /// The native visual the windows must be created with to be compatible
+    /// with the config.
+    ///
+    /// `None` is returned when the platform has no such concept or the visual
+    /// couldn't be identified.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** available on X11 with Xlib and on GBM;
+    /// - **GLX:** the visual of the `GLXFBConfig`;
+    /// - **WGL / CGL:** always `None`.
+    fn native_visual_info(&self) -> Option<NativeVisual>;
+
     /// Whether the config is hardware accelerated.
     ///
     /// The meaning of this may vary from system to system. On some it could
     /// mean that you're using a software backend renderer, it could mean
     /// that you're using not the fastest available GPU, like in laptops
     /// with hybrid graphics.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** configs of displays backed by a software device, like
+    ///   llvmpipe, are never reported as hardware accelerated.
     fn hardware_accelerated(&self) -> bool;
 
     /// The type of the surfaces that can be created with this config.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** queried with `EGL_SURFACE_TYPE`;
+    /// - **GLX:** queried with `GLX_DRAWABLE_TYPE`;
+    /// - **WGL:** the pixmaps are the ones the config could draw to the bitmap;
+    /// - **CGL:** only windows are supported.
     fn config_surface_types(&self) -> ConfigSurfaceTypes;
 
+    /// Whether the window surfaces could be created with this config.
+    fn supports_windows(&self) -> bool {
+        self.config_surface_types().contains(ConfigSurfaceTypes::WINDOW)
+    }
+
+    /// Whether the pbuffer surfaces could be created with this config.
+    fn supports_pbuffers(&self) -> bool {
+        self.config_surface_types().contains(ConfigSurfaceTypes::PBUFFER)
+    }
+
+    /// Whether the pixmap surfaces could be created with this config.
+    fn supports_pixmaps(&self) -> bool {
+        self.config_surface_types().contains(ConfigSurfaceTypes::PIXMAP)
+    }
+
+    /// The maximum width of the pbuffer created with this config.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** queried with `EGL_MAX_PBUFFER_WIDTH`;
+    /// - **GLX:** queried with `GLX_MAX_PBUFFER_WIDTH`;
+    /// - **WGL / CGL:** always `0`, since the pbuffers aren't supported.
+    fn max_pbuffer_width(&self) -> u32;
+
+    /// The maximum height of the pbuffer created with this config.
+    ///
+    /// See [`Self::max_pbuffer_width`] for the platform specifics.
+    fn max_pbuffer_height(&self) -> u32;
+
+    /// The maximum number of pixels, that is the width multiplied by the
+    /// height, of the pbuffer created with this config.
+    ///
+    /// See [`Self::max_pbuffer_width`] for the platform specifics.
+    fn max_pbuffer_pixels(&self) -> u32;
+
     /// The [`crate::config::Api`] supported by the configuration.
     fn api(&self) -> Api;
 }
@@ -126,6 +222,16 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// The range of bits in the stencil buffer.
+    ///
+    /// See [`Self::with_depth_size_range`] for details.
+    #[inline]
+    pub fn with_stencil_size_range(mut self, stencil_size: RangeInclusive<u8>) -> Self {
+        self.template.stencil_size = *stencil_size.start();
+        self.template.max_stencil_size = Some(*stencil_size.end());
+        self
+    }
+
     /// Number of bits in the depth buffer.
     ///
     /// By default `0` is requested.
@@ -135,6 +241,20 @@ impl ConfigTemplateBuilder {
         self
     }
 
+    /// The range of bits in the depth buffer, like `24..=24` to avoid the
+    /// slower 32 bit formats.
+    ///
+    /// The platforms only take the minimum into account, so the configs with
+    /// more bits than the end of the range are filtered out afterwards.
+    ///
+    /// By default the maximum is not specified.
+    #[inline]
+    pub fn with_depth_size_range(mut self, depth_size: RangeInclusive<u8>) -> Self {
+        self.template.depth_size = *depth_size.start();
+        self.template.max_depth_size = Some(*depth_size.end());
+        self
+    }
+
     /// Whether multisampling configurations should be picked. The `num_samples`
     /// must be a power of two.
     ///
@@ -148,7 +268,17 @@ impl ConfigTemplateBuilder {
 
     /// The types of the surfaces that must be supported by the configuration.
     ///
+    /// The types are combined, so with
+    /// `ConfigSurfaceTypes::WINDOW | ConfigSurfaceTypes::PBUFFER` only the
+    /// configs usable for both windows and pbuffers are picked.
+    ///
     /// By default only the `WINDOW` bit is set.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL:** requiring `PBUFFER` returns [`ErrorKind::NotSupported`].
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     #[inline]
     pub fn with_surface_type(mut self, config_surface_types: ConfigSurfaceTypes) -> Self {
         self.template.config_surface_types = config_surface_types;
@@ -157,6 +287,11 @@ impl ConfigTemplateBuilder {
 
     /// The type of the color buffer.
     ///
+    /// The component sizes are the minimum ones, so requesting `10` bits per
+    /// component, e.g. for HDR, only matches the 10:10:10 and deeper configs.
+    /// The 10:10:10:2 configs additionally require the alpha size of `2`, see
+    /// [`Self::with_alpha_size`].
+    ///
     /// By default `RGB` buffer with all components sizes of `8` is requested.
     #[inline]
     pub fn with_buffer_type(mut self, color_buffer_type: ColorBufferType) -> Self {
@@ -166,21 +301,43 @@ impl ConfigTemplateBuilder {
 
     /// The set of apis that are supported by this configuration.
     ///
-    /// The default [`Api`] depends on the used graphics platform interface. If
-    /// you want to do config filtering based on the [`Api`] yourself, use
-    /// [`Api::empty`].
+    /// The returned configs support all the requested apis, so requesting
+    /// [`Api::GLES3`] ensures that an OpenGL ES 3 context could be created with
+    /// them. The default [`Api`] depends on the used graphics platform
+    /// interface. If you want to do config filtering based on the [`Api`]
+    /// yourself, use [`Api::empty`].
     ///
     /// # Api-specific
     ///
     /// - **EGL:** [`Api::GLES2`] bit is set by default to avoid matching
-    ///   [`Api::GLES1`] configs;
+    ///   [`Api::GLES1`] configs. [`Api::GLES3`] requires EGL 1.5 or
+    ///   `EGL_KHR_create_context`, otherwise [`ErrorKind::NotSupported`] is
+    ///   returned when finding configs;
     /// - **GLX/WGL/CGL:** [`Api::OPENGL`] is always present in the result.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     #[inline]
     pub fn with_api(mut self, api: Api) -> Self {
         self.template.api = Some(api);
         self
     }
 
+    /// Require the configs to be conformant for the given [`Api`], which
+    /// excludes the configs the drivers expose, but which may fail the Api
+    /// conformance tests.
+    ///
+    /// By default the conformance isn't checked.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** sets the `EGL_CONFORMANT` mask.
+    /// - **GLX/WGL/CGL:** ignored.
+    #[inline]
+    pub fn with_conformant(mut self, api: Api) -> Self {
+        self.template.conformant = Some(api);
+        self
+    }
+
     /// Whether the stereo pairs should be present.
     ///
     /// By default it isn't specified.
@@ -225,11 +382,38 @@ impl ConfigTemplateBuilder {
     /// not.
     ///
     /// By default hardware acceleration or its absence is not requested.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** uses `EGL_CONFIG_CAVEAT`, and when hardware acceleration is
+    ///   requested no configs are returned for displays backed by a software
+    ///   device, since those don't mark their configs as slow;
+    /// - **GLX:** uses `GLX_CONFIG_CAVEAT`;
+    /// - **WGL:** uses `WGL_ACCELERATION_ARB`;
+    /// - **CGL:** only requesting hardware acceleration is supported.
     pub fn prefer_hardware_accelerated(mut self, hardware_accerelated: Option<bool>) -> Self {
         self.template.hardware_accelerated = hardware_accerelated;
         self
     }
 
+    /// Whether the config could be backed by the offline renderers, like the
+    /// GPU that is not driving any display.
+    ///
+    /// Allowing the offline renderers lets the context keep rendering when
+    /// the active display switches GPUs, instead of forcing the switch to the
+    /// discrete one.
+    ///
+    /// By default the offline renderers are allowed.
+    ///
+    /// # Api-specific
+    ///
+    /// This is CGL specific, where it uses `kCGLPFAAllowOfflineRenderers`.
+    #[inline]
+    pub fn with_allow_offline_renderers(mut self, allow_offline_renderers: bool) -> Self {
+        self.template.allow_offline_renderers = allow_offline_renderers;
+        self
+    }
+
     /// Request config that can render to a particular native window.
     ///
     /// # Platform-specific
@@ -238,8 +422,13 @@ impl ConfigTemplateBuilder {
     /// suitable for rendering into that window.
     ///
     /// When using WGL it's the most reliable way to get a working
-    /// configuration. With GLX it'll use the visual passed in
-    /// `native_window` to match the config.
+    /// configuration. With GLX and EGL on X11 it'll use the visual of the
+    /// `native_window` to match the config, so the surface creation doesn't
+    /// fail with [`ErrorKind::BadMatch`]. The visual is queried from the X
+    /// server when the handle doesn't carry it, which requires the display to
+    /// be created from the Xlib handle for EGL.
+    ///
+    /// [`ErrorKind::BadMatch`]: crate::error::ErrorKind::BadMatch
     pub fn compatible_with_native_window(mut self, native_window: RawWindowHandle) -> Self {
         self.template.native_window = Some(native_window);
         self
@@ -282,9 +471,15 @@ pub struct ConfigTemplate {
     /// Bits of depth in the depth buffer.
     pub(crate) depth_size: u8,
 
+    /// The maximum bits of depth in the depth buffer.
+    pub(crate) max_depth_size: Option<u8>,
+
     /// Bits of stencil in the stencil buffer.
     pub(crate) stencil_size: u8,
 
+    /// The maximum bits of stencil in the stencil buffer.
+    pub(crate) max_stencil_size: Option<u8>,
+
     /// The amount of samples in multisample buffer.
     pub(crate) num_samples: Option<u8>,
 
@@ -300,6 +495,9 @@ pub struct ConfigTemplate {
     /// The rendering Api's supported by the configuration.
     pub(crate) api: Option<Api>,
 
+    /// The Api's the configuration must be conformant for.
+    pub(crate) conformant: Option<Api>,
+
     /// The config should support transparency.
     pub(crate) transparency: bool,
 
@@ -323,6 +521,9 @@ pub struct ConfigTemplate {
 
     /// The native window config should support rendering into.
     pub(crate) native_window: Option<RawWindowHandle>,
+
+    /// The config could be backed by the offline renderers.
+    pub(crate) allow_offline_renderers: bool,
 }
 
 impl Default for ConfigTemplate {
@@ -334,8 +535,12 @@ impl Default for ConfigTemplate {
 
             depth_size: 24,
 
+            max_depth_size: None,
+
             stencil_size: 8,
 
+            max_stencil_size: None,
+
             num_samples: None,
 
             transparency: false,
@@ -358,11 +563,114 @@ impl Default for ConfigTemplate {
             native_window: None,
             hardware_accelerated: None,
 
+            allow_offline_renderers: true,
+
             api: None,
+
+            conformant: None,
         }
     }
 }
 
+impl ConfigTemplate {
+    /// Whether the `config` doesn't exceed the maximum sizes, which the
+    /// platforms can't filter by.
+    pub(crate) fn within_max_sizes(&self, config: &impl GlConfig) -> bool {
+        self.max_depth_size.map_or(true, |max| config.depth_size() <= max)
+            && self.max_stencil_size.map_or(true, |max| config.stencil_size() <= max)
+    }
+}
+
+/// The helper to pick the best config out of the ones returned by
+/// [`GlDisplay::find_configs`] according to the declared preferences.
+///
+/// The requirements are applied first, then the configs are compared by
+/// hardware acceleration, transparency, sRGB capability and the number of
+/// samples, in that order. The first config wins the ties, keeping the order
+/// of the platform.
+///
+/// ```no_run
+/// # use glutin::config::{ConfigScorer, ConfigTemplateBuilder};
+/// # use glutin::prelude::*;
+/// # let gl_display: glutin::display::Display = unimplemented!();
+/// let template = ConfigTemplateBuilder::new().build();
+/// let configs = unsafe { gl_display.find_configs(template).unwrap() };
+/// let config = ConfigScorer::new()
+///     .with_multisampling(true)
+///     .with_transparency(true)
+///     .pick_best(configs)
+///     .unwrap();
+/// ```
+///
+/// [`GlDisplay::find_configs`]: crate::display::GlDisplay::find_configs
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigScorer {
+    multisampling: bool,
+    srgb: bool,
+    transparency: bool,
+    require_alpha: bool,
+}
+
+impl ConfigScorer {
+    /// Create the scorer without any preferences.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Prefer the configs with the most samples when `true`, otherwise the
+    /// ones with the fewest.
+    ///
+    /// By default the fewest samples are preferred.
+    pub fn with_multisampling(mut self, multisampling: bool) -> Self {
+        self.multisampling = multisampling;
+        self
+    }
+
+    /// Prefer the sRGB capable configs.
+    ///
+    /// By default the sRGB capability is not taken into account.
+    pub fn with_srgb(mut self, srgb: bool) -> Self {
+        self.srgb = srgb;
+        self
+    }
+
+    /// Prefer the configs supporting transparency.
+    ///
+    /// By default the transparency is not taken into account.
+    pub fn with_transparency(mut self, transparency: bool) -> Self {
+        self.transparency = transparency;
+        self
+    }
+
+    /// Only accept the configs with the alpha channel.
+    ///
+    /// By default the alpha channel is not required.
+    pub fn with_required_alpha(mut self, require_alpha: bool) -> Self {
+        self.require_alpha = require_alpha;
+        self
+    }
+
+    /// Pick the best config out of the `configs`.
+    ///
+    /// [`None`] is returned when no config meets the requirements.
+    pub fn pick_best<C: GlConfig>(&self, configs: impl IntoIterator<Item = C>) -> Option<C> {
+        configs
+            .into_iter()
+            .filter(|config| !self.require_alpha || config.alpha_size() > 0)
+            .map(|config| (self.score(&config), config))
+            .reduce(|best, candidate| if candidate.0 > best.0 { candidate } else { best })
+            .map(|(_, config)| config)
+    }
+
+    fn score(&self, config: &impl GlConfig) -> (bool, bool, bool, i16) {
+        let transparency = self.transparency && config.supports_transparency().unwrap_or(false);
+        let srgb = self.srgb && config.srgb_capable();
+        let samples = config.num_samples() as i16;
+        let samples = if self.multisampling { samples } else { -samples };
+        (config.hardware_accelerated(), transparency, srgb, samples)
+    }
+}
+
 bitflags! {
     /// The types of the surface supported by the config.
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -413,6 +721,51 @@ pub enum ColorBufferType {
     Luminance(u8),
 }
 
+impl ColorBufferType {
+    /// The size of the smallest color component in bits, e.g. `10` for the
+    /// 10:10:10 buffers and `5` for the 5:6:5 ones.
+    ///
+    /// This could be used to compare the precision of the configs, like
+    /// preferring 10 bits per channel for HDR.
+    pub fn min_component_size(&self) -> u8 {
+        match *self {
+            Self::Rgb { r_size, g_size, b_size } => r_size.min(g_size).min(b_size),
+            Self::Luminance(size) => size,
+        }
+    }
+}
+
+/// The type of the color components, see [`GlConfig::color_component_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentType {
+    /// The normalized fixed point components.
+    Fixed,
+
+    /// The floating point components.
+    Float,
+}
+
+/// The native visual of the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NativeVisual {
+    /// The X11 visual.
+    X11 {
+        /// The `VisualID` to create the window with.
+        visual_id: u64,
+
+        /// The depth of the visual.
+        depth: u8,
+
+        /// The alpha mask of the visual, non-zero mask means that the visual
+        /// supports transparency.
+        alpha_mask: u16,
+    },
+
+    /// The DRM fourcc of the buffers, like the one used to create the GBM
+    /// surface.
+    Drm(u32),
+}
+
 /// The GL configuration used to create [`Surface`] and [`Context`] in a cross
 /// platform way.
 ///
@@ -471,6 +824,10 @@ impl GlConfig for Config {
         gl_api_dispatch!(self; Self(config) => config.num_samples())
     }
 
+    fn num_sample_buffers(&self) -> u8 {
+        gl_api_dispatch!(self; Self(config) => config.num_sample_buffers())
+    }
+
     fn srgb_capable(&self) -> bool {
         gl_api_dispatch!(self; Self(config) => config.srgb_capable())
     }
@@ -479,6 +836,18 @@ impl GlConfig for Config {
         gl_api_dispatch!(self; Self(config) => config.config_surface_types())
     }
 
+    fn max_pbuffer_width(&self) -> u32 {
+        gl_api_dispatch!(self; Self(config) => config.max_pbuffer_width())
+    }
+
+    fn max_pbuffer_height(&self) -> u32 {
+        gl_api_dispatch!(self; Self(config) => config.max_pbuffer_height())
+    }
+
+    fn max_pbuffer_pixels(&self) -> u32 {
+        gl_api_dispatch!(self; Self(config) => config.max_pbuffer_pixels())
+    }
+
     fn hardware_accelerated(&self) -> bool {
         gl_api_dispatch!(self; Self(config) => config.hardware_accelerated())
     }
@@ -487,6 +856,10 @@ impl GlConfig for Config {
         gl_api_dispatch!(self; Self(config) => config.supports_transparency())
     }
 
+    fn native_visual_info(&self) -> Option<NativeVisual> {
+        gl_api_dispatch!(self; Self(config) => config.native_visual_info())
+    }
+
     fn api(&self) -> Api {
         gl_api_dispatch!(self; Self(config) => config.api())
     }
@@ -505,6 +878,10 @@ impl X11GlConfigExt for Config {
     fn x11_visual(&self) -> Option<X11VisualInfo> {
         gl_api_dispatch!(self; Self(config) => config.x11_visual())
     }
+
+    fn x11_visual_id(&self) -> Option<u32> {
+        gl_api_dispatch!(self; Self(config) => config.x11_visual_id())
+    }
 }
 
 impl Sealed for Config {}
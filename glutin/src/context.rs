@@ -3,13 +3,14 @@
 #![allow(unreachable_patterns)]
 use std::ffi;
 
+use bitflags::bitflags;
 use raw_window_handle::RawWindowHandle;
 
 use crate::config::{Config, GetGlConfig};
 use crate::display::{Display, GetGlDisplay};
-use crate::error::Result;
+use crate::error::{ErrorKind, Result};
 use crate::private::{gl_api_dispatch, Sealed};
-use crate::surface::{GlSurface, Surface, SurfaceTypeTrait};
+use crate::surface::{GlSurface, RawSurface, Surface, SurfaceTypeTrait};
 
 #[cfg(cgl_backend)]
 use crate::api::cgl::context::{
@@ -32,7 +33,11 @@ use crate::api::wgl::context::{
 pub trait GlContext: Sealed {
     /// Get the [`ContextApi`] used by the context.
     ///
-    /// The returned value's [`Version`] will always be `None`.
+    /// The returned value's [`Version`] is the one requested when creating
+    /// the context, like the core version picked for
+    /// [`ContextApi::OpenGl`] without the version. It's `None` when unknown,
+    /// which is always the case on CGL and for the contexts wrapped from the
+    /// raw handles.
     fn context_api(&self) -> ContextApi;
 }
 
@@ -53,6 +58,11 @@ pub trait NotCurrentGlContext: Sealed {
     /// [`Self::PossiblyCurrentContext`] indicating that the context could
     /// be current on the thread.
     ///
+    /// [`ErrorKind::ContextLost`] is returned when the context was lost, e.g.
+    /// due to a power management event or a GPU reset.
+    ///
+    /// [`ErrorKind::ContextLost`]: crate::error::ErrorKind::ContextLost
+    ///
     /// # Platform specific
     ///
     /// - **macOS: this will block if your main thread is blocked**;
@@ -67,13 +77,18 @@ pub trait NotCurrentGlContext: Sealed {
     /// The same as [`Self::make_current`], but provides a way to set read and
     /// draw surfaces.
     ///
+    /// The surfaces could be of different types, which allows, for example,
+    /// reading from a pbuffer while drawing into a window.
+    ///
     /// # Api-specific:
     ///
-    /// - **WGL/CGL:** not supported.
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    /// - **WGL/CGL:** not supported, [`ErrorKind::NotSupported`] is returned.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<Self::PossiblyCurrentContext>;
 }
 
@@ -86,12 +101,35 @@ pub trait PossiblyCurrentGlContext: Sealed {
     type Surface<T: SurfaceTypeTrait>: GlSurface<T>;
 
     /// Returns `true` if this context is the current one in this thread.
+    ///
+    /// The context is compared against the one returned by the platform's
+    /// `GetCurrentContext`, so this could be used to assert that the GL calls
+    /// are issued from the right thread.
+    ///
+    /// # Platform specific
+    ///
+    /// - **EGL:** the Api bound on the calling thread is preserved.
     fn is_current(&self) -> bool;
 
+    /// The raw draw and read surfaces the platform has bound with the context
+    /// on the calling thread, or [`None`] when the context is not current.
+    ///
+    /// # Platform specific
+    ///
+    /// - **EGL:** the surfaces are null when the context is current without
+    ///   them. The Api bound on the calling thread is preserved.
+    /// - **WGL:** both are the window of the current device context.
+    /// - **CGL:** both are the view of the context.
+    fn query_current_surface(&self) -> Option<(RawSurface, RawSurface)>;
+
     /// Make the context not current to the current thread and returns a
     /// [`Self::NotCurrentContext`] to indicate that the context is a not
     /// current to allow sending it to the different thread.
     ///
+    /// The previously current context is flushed, unless it was created with
+    /// [`ReleaseBehavior::None`], so the call doesn't stall on the pending
+    /// commands.
+    ///
     /// # Platform specific
     ///
     /// - **macOS: this will block if your main thread is blocked.**
@@ -99,6 +137,17 @@ pub trait PossiblyCurrentGlContext: Sealed {
 
     /// Make [`Self::Surface`] current on the calling thread.
     ///
+    /// [`ErrorKind::ContextLost`] is returned when the context was lost, e.g.
+    /// due to a power management event or a GPU reset.
+    ///
+    /// [`ErrorKind::ContextLost`]: crate::error::ErrorKind::ContextLost
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, when the context is still current on another thread,
+    /// as tracked by glutin. The platforms fail with the opaque error in this
+    /// case, like `EGL_BAD_ACCESS`.
+    ///
     /// # Platform specific
     ///
     /// - **macOS: this will block if your main thread is blocked.**
@@ -107,13 +156,18 @@ pub trait PossiblyCurrentGlContext: Sealed {
     /// The same as [`Self::make_current`] but provides a way to set read and
     /// draw surfaces explicitly.
     ///
+    /// The surfaces could be of different types, which allows, for example,
+    /// reading from a pbuffer while drawing into a window.
+    ///
     /// # Api-specific:
     ///
-    /// - **CGL/WGL:** not supported.
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    /// - **CGL/WGL:** not supported, [`ErrorKind::NotSupported`] is returned.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<()>;
 }
 
@@ -141,11 +195,51 @@ impl ContextAttributesBuilder {
     /// This option is ignored when using [`Robustness::NoError`].
     ///
     /// The default value for this flag is `false`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **GLX:** combining it with [`Robustness::NoError`] is rejected with
+    ///   [`ErrorKind::InvalidContextAttributes`], since the driver fails with
+    ///   `GLXBadContext` otherwise.
+    ///
+    /// [`ErrorKind::InvalidContextAttributes`]: crate::error::ErrorKind::InvalidContextAttributes
     pub fn with_debug(mut self, debug: bool) -> Self {
         self.attributes.debug = debug;
         self
     }
 
+    /// Set the context creation [`ContextFlags`].
+    ///
+    /// Every attribute the flags cover is assigned from them, so the missing
+    /// flags reset it to the default: [`ContextFlags::DEBUG`] sets the same
+    /// attribute as [`Self::with_debug`], [`ContextFlags::ROBUST`] and
+    /// [`ContextFlags::NO_ERROR`] set the [`Robustness`] to
+    /// [`Robustness::RobustNoResetNotification`] and [`Robustness::NoError`]
+    /// respectively, and [`Robustness::NotRobust`] without either of them.
+    ///
+    /// The calls are applied in order, so the later [`Self::with_debug`] or
+    /// [`Self::with_robustness`] override what the flags set, and the other
+    /// way around.
+    ///
+    /// Combining [`ContextFlags::ROBUST`] with [`ContextFlags::NO_ERROR`] is
+    /// contradictory, and the context creation fails with
+    /// [`ErrorKind::InvalidContextAttributes`], unless the robustness is set
+    /// explicitly afterwards.
+    pub fn with_flags(mut self, flags: ContextFlags) -> Self {
+        self.attributes.debug = flags.contains(ContextFlags::DEBUG);
+        self.attributes.forward_compatible = flags.contains(ContextFlags::FORWARD_COMPATIBLE);
+        self.attributes.robustness = if flags.contains(ContextFlags::NO_ERROR) {
+            Robustness::NoError
+        } else if flags.contains(ContextFlags::ROBUST) {
+            Robustness::RobustNoResetNotification
+        } else {
+            Robustness::NotRobust
+        };
+        self.attributes.conflicting_flags =
+            flags.contains(ContextFlags::ROBUST | ContextFlags::NO_ERROR);
+        self
+    }
+
     /// Share the display lists with the given context.
     ///
     /// To get sharing working it's recommended to use the same [`Config`] when
@@ -169,6 +263,28 @@ impl ContextAttributesBuilder {
     /// should consider [`Robustness::RobustLoseContextOnReset`].
     pub fn with_robustness(mut self, robustness: Robustness) -> Self {
         self.attributes.robustness = robustness;
+        self.attributes.conflicting_flags = false;
+        self
+    }
+
+    /// Sets the reset notification strategy of the context independently of
+    /// the robust buffer access. See the docs of [`ResetNotification`].
+    ///
+    /// By default the strategy is derived from the [`Robustness`], that is
+    /// [`Robustness::RobustLoseContextOnReset`] requests
+    /// [`ResetNotification::LoseContextOnReset`] and
+    /// [`Robustness::RobustNoResetNotification`] requests
+    /// [`ResetNotification::NoResetNotification`], while other variants don't
+    /// request any. With [`Robustness::NotRobust`] the strategy is requested
+    /// without the robust buffer access.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** maps to `EGL_CONTEXT_OPENGL_RESET_NOTIFICATION_STRATEGY`.
+    /// - **GLX:** can't be combined with [`Robustness::NoError`].
+    /// - **macOS:** not supported.
+    pub fn with_reset_notification(mut self, reset_notification: ResetNotification) -> Self {
+        self.attributes.reset_notification = Some(reset_notification);
         self
     }
 
@@ -204,6 +320,9 @@ impl ContextAttributesBuilder {
     /// Build the context attributes.
     ///
     /// The `raw_window_handle` isn't required and here for WGL compatibility.
+    /// Passing [`None`] is valid, so the context could be created right after
+    /// picking the config, before any window exists, and made current with a
+    /// window surface later, as long as that surface uses the same config.
     ///
     /// # Api-specific
     ///
@@ -222,8 +341,15 @@ pub struct ContextAttributes {
 
     pub(crate) debug: bool,
 
+    pub(crate) forward_compatible: bool,
+
     pub(crate) robustness: Robustness,
 
+    /// Both [`ContextFlags::ROBUST`] and [`ContextFlags::NO_ERROR`] were set.
+    pub(crate) conflicting_flags: bool,
+
+    pub(crate) reset_notification: Option<ResetNotification>,
+
     pub(crate) profile: Option<GlProfile>,
 
     pub(crate) api: Option<ContextApi>,
@@ -233,6 +359,105 @@ pub struct ContextAttributes {
     pub(crate) raw_window_handle: Option<RawWindowHandle>,
 }
 
+impl ContextAttributes {
+    /// Check that the attributes don't contradict each other.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.conflicting_flags {
+            return Err(ErrorKind::InvalidContextAttributes(
+                "the context can't be both robust and without the error reporting",
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// The reset notification strategy to request, if any.
+    pub(crate) fn reset_notification(&self) -> Option<ResetNotification> {
+        self.reset_notification.or(match self.robustness {
+            Robustness::RobustLoseContextOnReset => Some(ResetNotification::LoseContextOnReset),
+            Robustness::RobustNoResetNotification => Some(ResetNotification::NoResetNotification),
+            Robustness::NotRobust | Robustness::NoError => None,
+        })
+    }
+
+    /// Create the context with the highest core version accepted by `create`
+    /// when the OpenGL context of unspecified version was requested, returning
+    /// it with that version.
+    ///
+    /// The lower versions are tried only while `create` fails with
+    /// [`ErrorKind::BadMatch`], which the platforms report for the unsupported
+    /// versions, so the other errors don't repeat for every version.
+    ///
+    /// [`None`] is returned when the attributes don't ask for that or none of
+    /// the versions was accepted, so the context should be created as is.
+    #[cfg(any(egl_backend, glx_backend, wgl_backend))]
+    pub(crate) fn with_highest_core_version<T>(
+        &self,
+        mut create: impl FnMut(&ContextAttributes) -> Result<T>,
+    ) -> Option<(T, Version)> {
+        if self.api != Some(ContextApi::OpenGl(None))
+            || self.profile == Some(GlProfile::Compatibility)
+        {
+            return None;
+        }
+
+        let mut attributes = self.clone();
+        attributes.profile = Some(GlProfile::Core);
+        for version in CORE_VERSIONS {
+            attributes.api = Some(ContextApi::OpenGl(Some(version)));
+            match create(&attributes) {
+                Ok(context) => return Some((context, version)),
+                Err(err) if err.error_kind() == ErrorKind::BadMatch => continue,
+                Err(_) => return None,
+            }
+        }
+
+        None
+    }
+}
+
+/// The core OpenGL versions tried for [`ContextApi::OpenGl`] without the
+/// version, from the highest to the lowest.
+#[cfg(any(egl_backend, glx_backend, wgl_backend))]
+const CORE_VERSIONS: [Version; 9] = [
+    Version::new(4, 6),
+    Version::new(4, 5),
+    Version::new(4, 4),
+    Version::new(4, 3),
+    Version::new(4, 2),
+    Version::new(4, 1),
+    Version::new(4, 0),
+    Version::new(3, 3),
+    Version::new(3, 2),
+];
+
+bitflags! {
+    /// The flags the context is created with, see
+    /// [`ContextAttributesBuilder::with_flags`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ContextFlags: u8 {
+        /// Remove the deprecated functionality from OpenGL 3.0+ contexts,
+        /// which some strict drivers require for the core profile.
+        ///
+        /// # Api-specific
+        ///
+        /// - **macOS:** ignored, the core profile contexts are always forward
+        ///   compatible.
+        const FORWARD_COMPATIBLE = 0b0001;
+
+        /// Create the debug context.
+        const DEBUG              = 0b0010;
+
+        /// Create the context with the robust buffer access.
+        const ROBUST             = 0b0100;
+
+        /// Create the context without the error reporting, see
+        /// [`Robustness::NoError`].
+        const NO_ERROR           = 0b1000;
+    }
+}
+
 /// Specifies the tolerance of the OpenGL context to faults. If you accept
 /// raw OpenGL commands and/or raw shader code from an untrusted source, you
 /// should definitely care about this.
@@ -263,6 +488,19 @@ pub enum Robustness {
     RobustLoseContextOnReset,
 }
 
+/// The behavior of the context when the graphics reset happens, see
+/// [`ContextAttributesBuilder::with_reset_notification`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResetNotification {
+    /// The reset isn't reported and the context continues to be usable,
+    /// though its behavior is undefined.
+    NoResetNotification,
+
+    /// The reset is reported and the context enters the "context lost" state,
+    /// so it must be recreated.
+    LoseContextOnReset,
+}
+
 /// Describes the requested OpenGL context profiles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GlProfile {
@@ -281,9 +519,14 @@ pub enum GlProfile {
 pub enum ContextApi {
     /// OpenGL Api version that should be used by the context.
     ///
-    /// When using `None` as `Version` any OpenGL context will be picked,
-    /// however when the [`GlProfile::Core`] is used at least 3.3 will be
-    /// requested.
+    /// When using `None` as `Version` the highest core version the driver
+    /// supports is requested, by trying the versions from 4.6 down to 3.2
+    /// and picking the first one that could be created, which
+    /// [`GlContext::context_api`] then reports. The core profile is requested
+    /// unless the [`GlProfile::Compatibility`] is set, so the applications
+    /// relying on the fixed-function pipeline must request it explicitly.
+    /// When it's set or the versioned context creation isn't supported any
+    /// OpenGL context will be picked instead.
     OpenGl(Option<Version>),
 
     /// OpenGL Api version that should be used by the context.
@@ -291,6 +534,13 @@ pub enum ContextApi {
     /// When using `None` as `Version` the latest **known** major version is
     /// picked. Versions that are higher than what was picked automatically
     /// could still be supported.
+    ///
+    /// When the `Version` is specified, both major and minor versions are
+    /// requested and the context creation fails if they can't be provided,
+    /// e.g. EGL without `EGL_KHR_create_context` can only request the major
+    /// version, thus [`ErrorKind::NotSupported`] is returned for ES 3.2.
+    ///
+    /// [`ErrorKind::NotSupported`]: crate::error::ErrorKind::NotSupported
     Gles(Option<Version>),
 }
 
@@ -335,6 +585,9 @@ pub enum ReleaseBehavior {
     ///
     /// # Api-specific
     ///
+    /// - **EGL:** requires `EGL_KHR_context_flush_control`.
+    /// - **GLX:** requires `GLX_ARB_context_flush_control`.
+    /// - **WGL:** requires `WGL_ARB_context_flush_control`.
     /// - **macOS:** not supported, [`Self::Flush`] is always used.
     None,
 
@@ -411,10 +664,10 @@ impl NotCurrentGlContext for NotCurrentContext {
         }
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<Self::PossiblyCurrentContext> {
         match (self, surface_draw, surface_read) {
             #[cfg(egl_backend)]
@@ -511,6 +764,10 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
         gl_api_dispatch!(self; Self(context) => context.is_current())
     }
 
+    fn query_current_surface(&self) -> Option<(RawSurface, RawSurface)> {
+        gl_api_dispatch!(self; Self(context) => context.query_current_surface())
+    }
+
     fn make_not_current(self) -> Result<Self::NotCurrentContext> {
         Ok(
             gl_api_dispatch!(self; Self(context) => context.make_not_current()?; as NotCurrentContext),
@@ -531,10 +788,10 @@ impl PossiblyCurrentGlContext for PossiblyCurrentContext {
         }
     }
 
-    fn make_current_draw_read<T: SurfaceTypeTrait>(
+    fn make_current_draw_read<T: SurfaceTypeTrait, U: SurfaceTypeTrait>(
         &self,
         surface_draw: &Self::Surface<T>,
-        surface_read: &Self::Surface<T>,
+        surface_read: &Self::Surface<U>,
     ) -> Result<()> {
         match (self, surface_draw, surface_read) {
             #[cfg(egl_backend)]
@@ -588,6 +845,32 @@ impl AsRawContext for PossiblyCurrentContext {
 
 impl Sealed for PossiblyCurrentContext {}
 
+/// The objects sharing of the context being created with
+/// [`GlDisplay::create_context_with_sharing`].
+///
+/// Each context, shared or not, could be current on at most one thread at a
+/// time, so a context per thread should be created to render from multiple
+/// threads. The shared contexts could access the same objects, like textures
+/// and buffers, but the synchronization of the access to them is up to the
+/// user.
+///
+/// [`GlDisplay::create_context_with_sharing`]: crate::display::GlDisplay::create_context_with_sharing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sharing {
+    /// The context doesn't share objects with other contexts.
+    Isolated,
+
+    /// The context shares objects with the given context.
+    SharedWith(RawContext),
+}
+
+impl Sharing {
+    /// Share the objects with the given `context`.
+    pub fn shared_with(context: &impl AsRawContext) -> Self {
+        Self::SharedWith(context.raw_context())
+    }
+}
+
 /// Raw context.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RawContext {
@@ -0,0 +1,74 @@
+//! Tracking of the threads the contexts are current on in debug builds.
+//!
+//! Making the context current on a thread while it's still current on another
+//! one is a common mistake, which the platforms report with a generic error,
+//! like `EGL_BAD_ACCESS`, so panic with the clear message instead.
+//!
+//! Only the changes done through glutin are tracked.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
+
+use once_cell::sync::Lazy;
+
+/// The thread and the client Api the context is current for, keyed by the
+/// raw context.
+static CURRENT_CONTEXTS: Lazy<Mutex<HashMap<usize, (ThreadId, u32)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Panic if the `context` is current on the thread other than the calling one.
+pub(crate) fn check_make_current(context: usize) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let this_thread = thread::current().id();
+    let current_thread = current_contexts().get(&context).map(|&(thread, _)| thread);
+    // The lock is released at this point, so the panic doesn't poison it.
+    if let Some(thread) = current_thread.filter(|thread| *thread != this_thread) {
+        panic!(
+            "the context {context:#x} is made current on {this_thread:?} while still being \
+             current on {thread:?}, make it not current there first"
+        );
+    }
+}
+
+/// Mark the `context` current on the calling thread for the given client
+/// `api`, replacing the context previously current for it.
+pub(crate) fn mark_current(context: usize, api: u32) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let mut current_contexts = current_contexts();
+    let this_thread = thread::current().id();
+    current_contexts.retain(|_, &mut current| current != (this_thread, api));
+    current_contexts.insert(context, (this_thread, api));
+}
+
+/// Mark that no context is current on the calling thread for the given client
+/// `api`.
+pub(crate) fn mark_not_current(api: u32) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let this_thread = thread::current().id();
+    current_contexts().retain(|_, &mut current| current != (this_thread, api));
+}
+
+/// Forget the destroyed `context`.
+pub(crate) fn forget(context: usize) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    current_contexts().remove(&context);
+}
+
+/// Lock the tracked contexts, ignoring the poisoning, since the map is always
+/// left consistent.
+fn current_contexts() -> MutexGuard<'static, HashMap<usize, (ThreadId, u32)>> {
+    CURRENT_CONTEXTS.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -1,19 +1,27 @@
 //! The OpenGL platform display selection and creation.
 #![allow(unreachable_patterns)]
 
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::ffi::{self, CStr};
 use std::fmt;
+use std::num::NonZeroU32;
+use std::ptr::NonNull;
 
 use bitflags::bitflags;
+use once_cell::sync::OnceCell;
 use raw_window_handle::RawDisplayHandle;
 
-use crate::config::{Config, ConfigTemplate, GlConfig};
-use crate::context::{ContextAttributes, NotCurrentContext, NotCurrentGlContext};
-use crate::error::Result;
+use crate::config::{Config, ConfigSurfaceTypes, ConfigTemplate, GlConfig};
+use crate::context::{
+    ContextAttributes, ContextAttributesBuilder, NotCurrentContext, NotCurrentGlContext,
+    PossiblyCurrentContext, PossiblyCurrentGlContext, Sharing, Version,
+};
+use crate::error::{Error, ErrorKind, Result};
 use crate::private::{gl_api_dispatch, Sealed};
 use crate::surface::{
-    GlSurface, PbufferSurface, PixmapSurface, Surface, SurfaceAttributes, WindowSurface,
+    GlSurface, PbufferSurface, PixmapSurface, Surface, SurfaceAttributes, SurfaceAttributesBuilder,
+    WindowSurface,
 };
 
 #[cfg(cgl_backend)]
@@ -54,8 +62,74 @@ pub trait GlDisplay: Sealed {
         template: ConfigTemplate,
     ) -> Result<Box<dyn Iterator<Item = Self::Config> + '_>>;
 
+    /// Find configs matching the given `template`, sorted with the `compare`
+    /// function.
+    ///
+    /// The sort is stable, so the configs comparing equal keep the order of
+    /// the platform. Unlike picking the single best config, the ranked list
+    /// allows falling back to the next config when the context creation fails
+    /// without querying the configs again.
+    ///
+    /// ```no_run
+    /// # use glutin::config::ConfigTemplateBuilder;
+    /// # use glutin::prelude::*;
+    /// # let gl_display: glutin::display::Display = unimplemented!();
+    /// let template = ConfigTemplateBuilder::new().build();
+    /// let configs = unsafe {
+    ///     gl_display.find_configs_sorted_by(template, |a, b| b.num_samples().cmp(&a.num_samples()))
+    /// }
+    /// .unwrap();
+    /// let top_three = &configs[..configs.len().min(3)];
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Self::find_configs`].
+    unsafe fn find_configs_sorted_by<F>(
+        &self,
+        template: ConfigTemplate,
+        compare: F,
+    ) -> Result<Vec<Self::Config>>
+    where
+        F: FnMut(&Self::Config, &Self::Config) -> Ordering,
+    {
+        let mut configs: Vec<_> = unsafe { self.find_configs(template)? }.collect();
+        configs.sort_by(compare);
+        Ok(configs)
+    }
+
     /// Create the graphics platform context.
     ///
+    /// When using the cross platform [`Display`], the message of the returned
+    /// error describes the requested context attributes and the config, while
+    /// keeping the raw code of the platform error.
+    ///
+    /// The context only depends on the `config`, so it could be created before
+    /// the window and its surface, which are attached later with
+    /// [`NotCurrentGlContext::make_current`].
+    ///
+    /// ```no_run
+    /// # use std::num::NonZeroU32;
+    /// # use glutin::config::Config;
+    /// # use glutin::context::ContextAttributesBuilder;
+    /// # use glutin::display::GetGlDisplay;
+    /// # use glutin::prelude::*;
+    /// # use glutin::surface::{SurfaceAttributesBuilder, WindowSurface};
+    /// # let gl_config: Config = unimplemented!();
+    /// # let raw_window_handle: raw_window_handle::RawWindowHandle = unimplemented!();
+    /// # let (width, height) = (NonZeroU32::new(1).unwrap(), NonZeroU32::new(1).unwrap());
+    /// let gl_display = gl_config.display();
+    /// let context_attributes = ContextAttributesBuilder::new().build(None);
+    /// let not_current = unsafe { gl_display.create_context(&gl_config, &context_attributes) }?;
+    ///
+    /// // Later, once the window exists.
+    /// let surface_attributes =
+    ///     SurfaceAttributesBuilder::<WindowSurface>::new().build(raw_window_handle, width, height);
+    /// let surface = unsafe { gl_display.create_window_surface(&gl_config, &surface_attributes) }?;
+    /// let gl_context = not_current.make_current(&surface)?;
+    /// # Ok::<(), glutin::error::Error>(())
+    /// ```
+    ///
     /// # Safety
     ///
     /// Some platforms use [`RawWindowHandle`] for context creation, so it must
@@ -71,14 +145,99 @@ pub trait GlDisplay: Sealed {
     ///
     /// [`RawWindowHandle`]: raw_window_handle::RawWindowHandle
     /// [`not current`]: crate::context::PossiblyCurrentGlContext::make_not_current
+    /// [`NotCurrentGlContext::make_current`]: crate::context::NotCurrentGlContext::make_current
     unsafe fn create_context(
         &self,
         config: &Self::Config,
         context_attributes: &ContextAttributes,
     ) -> Result<Self::NotCurrentContext>;
 
+    /// Create the graphics platform context trying each of the
+    /// `context_attributes` in order, returning the first created context
+    /// together with the index of the attributes it was created with.
+    ///
+    /// When all the attempts fail, the error contains the messages of all of
+    /// them and the kind of the last one. [`ErrorKind::BadParameter`] is
+    /// returned when no attributes were passed.
+    ///
+    /// ```no_run
+    /// # use glutin::context::{ContextApi, ContextAttributesBuilder, Version};
+    /// # use glutin::prelude::*;
+    /// # let (gl_display, gl_config): (glutin::display::Display, glutin::config::Config) = unimplemented!();
+    /// # let raw_window_handle = None;
+    /// let attributes = [
+    ///     ContextAttributesBuilder::new().build(raw_window_handle),
+    ///     ContextAttributesBuilder::new()
+    ///         .with_context_api(ContextApi::Gles(None))
+    ///         .build(raw_window_handle),
+    ///     ContextAttributesBuilder::new()
+    ///         .with_context_api(ContextApi::OpenGl(Some(Version::new(2, 1))))
+    ///         .build(raw_window_handle),
+    /// ];
+    ///
+    /// let (context, _) =
+    ///     unsafe { gl_display.create_context_with_fallbacks(&gl_config, &attributes).unwrap() };
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Self::create_context`].
+    unsafe fn create_context_with_fallbacks(
+        &self,
+        config: &Self::Config,
+        context_attributes: &[ContextAttributes],
+    ) -> Result<(Self::NotCurrentContext, usize)> {
+        let mut errors = Vec::with_capacity(context_attributes.len());
+        for (index, attributes) in context_attributes.iter().enumerate() {
+            match unsafe { self.create_context(config, attributes) } {
+                Ok(context) => return Ok((context, index)),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        let kind = match errors.last() {
+            Some(err) => err.error_kind(),
+            None => return Err(ErrorKind::BadParameter.into()),
+        };
+        let message = errors
+            .iter()
+            .enumerate()
+            .map(|(index, err)| format!("attributes #{index}: {err}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(Error::new(None, Some(format!("failed to create context with {message}")), kind))
+    }
+
+    /// Create the graphics platform context with the explicit [`Sharing`],
+    /// which overrides the one set with
+    /// [`ContextAttributesBuilder::with_sharing`].
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Self::create_context`].
+    ///
+    /// [`ContextAttributesBuilder::with_sharing`]: crate::context::ContextAttributesBuilder::with_sharing
+    unsafe fn create_context_with_sharing(
+        &self,
+        config: &Self::Config,
+        context_attributes: &ContextAttributes,
+        sharing: Sharing,
+    ) -> Result<Self::NotCurrentContext> {
+        let mut context_attributes = context_attributes.clone();
+        context_attributes.shared_context = match sharing {
+            Sharing::Isolated => None,
+            Sharing::SharedWith(context) => Some(context),
+        };
+        unsafe { self.create_context(config, &context_attributes) }
+    }
+
     /// Create the surface that can be used to render into native window.
     ///
+    /// # Platform-specific
+    ///
+    /// - **Android:** the format of the `ANativeWindow` buffers is set to the
+    ///   native visual of the `config` with `ANativeWindow_setBuffersGeometry`.
+    ///
     /// # Safety
     ///
     /// The [`RawWindowHandle`] must point to a valid object.
@@ -121,9 +280,22 @@ pub trait GlDisplay: Sealed {
     ///
     /// - **WGL:** to load all the functions you must have a current context on
     ///   the calling thread, otherwise only a limited set of functions will be
-    ///   loaded.
+    ///   loaded. The resolved addresses are cached by the display, since
+    ///   `wglGetProcAddress` could be slow with some drivers.
     fn get_proc_address(&self, addr: &CStr) -> *const ffi::c_void;
 
+    /// Return the address of an OpenGL function like
+    /// [`Self::get_proc_address`], but [`None`] when the platform couldn't
+    /// resolve it.
+    ///
+    /// # Api-specific
+    ///
+    /// - **WGL:** the OpenGL 1.1 functions, which aren't returned by
+    ///   `wglGetProcAddress`, are loaded from `opengl32.dll`.
+    fn get_proc_address_checked(&self, addr: &CStr) -> Option<NonNull<ffi::c_void>> {
+        NonNull::new(self.get_proc_address(addr) as *mut ffi::c_void)
+    }
+
     /// Helper to obtain the information about the underlying display.
     ///
     /// This function is intended to be used for logging purposes to help with
@@ -208,9 +380,46 @@ impl Display {
     /// The `preference` must contain pointers to the valid values if GLX or WGL
     /// specific options were used.
     pub unsafe fn new(display: RawDisplayHandle, preference: DisplayApiPreference) -> Result<Self> {
+        unsafe { Self::with_gpu_preference(display, preference, GpuPreference::Default) }
+    }
+
+    /// Create a graphics platform display like [`Display::new`], hinting which
+    /// GPU should back it on multi GPU systems.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **EGL:** uses `EGL_ANGLE_display_power_preference` when the display is
+    ///   created with ANGLE, otherwise the hint is ignored.
+    /// - **CGL:** [`GpuPreference::HighPerformance`] doesn't allow offline
+    ///   renderers, which makes the system switch to the discrete GPU.
+    /// - **GLX / WGL:** the hint is ignored. On Windows the GPU is selected by
+    ///   the driver, which could be hinted by exporting `NvOptimusEnablement`
+    ///   and `AmdPowerXpressRequestHighPerformance` from the executable.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`Display::new`].
+    pub unsafe fn with_gpu_preference(
+        display: RawDisplayHandle,
+        preference: DisplayApiPreference,
+        gpu_preference: GpuPreference,
+    ) -> Result<Self> {
+        // Not every backend is able to honor the preference.
+        let _ = gpu_preference;
+
         match preference {
             #[cfg(egl_backend)]
-            DisplayApiPreference::Egl => unsafe { Ok(Self::Egl(EglDisplay::new(display)?)) },
+            DisplayApiPreference::Egl => unsafe {
+                Ok(Self::Egl(EglDisplay::with_gpu_preference(display, gpu_preference)?))
+            },
+            #[cfg(egl_backend)]
+            DisplayApiPreference::Angle(angle_backend) => unsafe {
+                Ok(Self::Egl(EglDisplay::with_angle_backend(
+                    display,
+                    angle_backend,
+                    gpu_preference,
+                )?))
+            },
             #[cfg(glx_backend)]
             DisplayApiPreference::Glx(registrar) => unsafe {
                 Ok(Self::Glx(GlxDisplay::new(display, registrar)?))
@@ -220,12 +429,12 @@ impl Display {
                 if let Ok(display) = GlxDisplay::new(display, registrar) {
                     Ok(Self::Glx(display))
                 } else {
-                    Ok(Self::Egl(EglDisplay::new(display)?))
+                    Ok(Self::Egl(EglDisplay::with_gpu_preference(display, gpu_preference)?))
                 }
             },
             #[cfg(all(egl_backend, glx_backend))]
             DisplayApiPreference::EglThenGlx(registrar) => unsafe {
-                if let Ok(display) = EglDisplay::new(display) {
+                if let Ok(display) = EglDisplay::with_gpu_preference(display, gpu_preference) {
                     Ok(Self::Egl(display))
                 } else {
                     Ok(Self::Glx(GlxDisplay::new(display, registrar)?))
@@ -237,7 +446,7 @@ impl Display {
             },
             #[cfg(all(egl_backend, wgl_backend))]
             DisplayApiPreference::EglThenWgl(window_handle) => unsafe {
-                if let Ok(display) = EglDisplay::new(display) {
+                if let Ok(display) = EglDisplay::with_gpu_preference(display, gpu_preference) {
                     Ok(Self::Egl(display))
                 } else {
                     Ok(Self::Wgl(WglDisplay::new(display, window_handle)?))
@@ -248,13 +457,185 @@ impl Display {
                 if let Ok(display) = WglDisplay::new(display, window_handle) {
                     Ok(Self::Wgl(display))
                 } else {
-                    Ok(Self::Egl(EglDisplay::new(display)?))
+                    Ok(Self::Egl(EglDisplay::with_gpu_preference(display, gpu_preference)?))
                 }
             },
             #[cfg(cgl_backend)]
-            DisplayApiPreference::Cgl => unsafe { Ok(Self::Cgl(CglDisplay::new(display)?)) },
+            DisplayApiPreference::Cgl => unsafe {
+                Ok(Self::Cgl(CglDisplay::with_gpu_preference(display, gpu_preference)?))
+            },
+        }
+    }
+
+    /// The version of the graphics platform interface backing the display,
+    /// like EGL 1.5.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **EGL:** the version reported by `eglInitialize`.
+    /// - **GLX:** the version reported by `glXQueryVersion`.
+    /// - **WGL / CGL:** always `None`, since they aren't versioned.
+    pub fn backend_version(&self) -> Option<Version> {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(display) => Some(display.version()),
+            #[cfg(glx_backend)]
+            Self::Glx(display) => Some(display.version()),
+            _ => None,
+        }
+    }
+
+    /// The vendor of the graphics platform interface implementation, which
+    /// is available before any context is created.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **EGL:** queried with `EGL_VENDOR`.
+    /// - **GLX:** queried with `GLX_VENDOR` from the server.
+    /// - **WGL / CGL:** always `None`.
+    pub fn vendor(&self) -> Option<String> {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(display) => display.vendor(),
+            #[cfg(glx_backend)]
+            Self::Glx(display) => display.vendor(),
+            _ => None,
+        }
+    }
+
+    /// Query the string of the graphics platform interface with the given
+    /// `name`, for the strings glutin doesn't expose otherwise.
+    ///
+    /// The `name` is the constant of the backing platform, so the caller must
+    /// check which one is in use.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **EGL:** queried with `eglQueryString`, e.g. for `EGL_CLIENT_APIS`.
+    /// - **GLX:** queried with `glXQueryServerString`.
+    /// - **WGL / CGL:** always `None`.
+    pub fn query_string(&self, name: i32) -> Option<String> {
+        // Not every backend is able to query the strings.
+        let _ = name;
+
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(display) => display.query_string(name),
+            #[cfg(glx_backend)]
+            Self::Glx(display) => display.query_server_string(name),
+            _ => None,
         }
     }
+
+    /// The known issues of the driver backing the display, detected from the
+    /// vendor and device strings, so the applications could work around them.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **EGL:** uses the `EGL_VENDOR`, the display extensions and the
+    ///   `EGL_EXT_device_query_name` strings of the device.
+    /// - **GLX / WGL / CGL:** always empty.
+    pub fn driver_quirks(&self) -> DriverQuirks {
+        match self {
+            #[cfg(egl_backend)]
+            Self::Egl(display) => display.driver_quirks(),
+            _ => DriverQuirks::empty(),
+        }
+    }
+
+    /// Route [`GlDisplay::get_proc_address`] through the given `loader`
+    /// instead of the platform function, so the OpenGL functions could be
+    /// resolved from the custom dispatch table, e.g. to wrap every call with
+    /// the tracing layer.
+    ///
+    /// The loader could be set only once, [`ErrorKind::BadAccess`] is returned
+    /// otherwise. It's shared by all the clones of the display.
+    pub fn set_proc_loader(&self, loader: ProcLoader) -> Result<()> {
+        gl_api_dispatch!(self; Self(display) => display.set_proc_loader(loader))
+    }
+
+    /// Present all the `surfaces` rendered with the `context` back-to-back, to
+    /// reduce the skew between them, e.g. for multi monitor setups.
+    ///
+    /// The rendering is flushed once before the swaps, so they don't wait on
+    /// it one by one, and the surfaces are made current when needed, leaving
+    /// the last one current. This doesn't synchronize the presentation of the
+    /// displays, which requires the hardware support like genlock, it only
+    /// avoids the work in between the swaps.
+    ///
+    /// The swaps are done one by one, so with the default
+    /// [`SwapInterval::Wait`] every swap may block until the vertical blank,
+    /// skewing the `surfaces` by up to a frame each. Set the
+    /// [`SwapInterval::DontWait`] on all the `surfaces` but the last one, or on
+    /// all of them when pacing the frames otherwise, before calling this
+    /// function. Making the surfaces current in between could also flush the
+    /// rendering on EGL and GLX.
+    ///
+    /// [`SwapInterval::Wait`]: crate::surface::SwapInterval::Wait
+    /// [`SwapInterval::DontWait`]: crate::surface::SwapInterval::DontWait
+    pub fn swap_buffers_all(
+        &self,
+        surfaces: &[&Surface<WindowSurface>],
+        context: &PossiblyCurrentContext,
+    ) -> Result<()> {
+        if let Some(surface) = surfaces.first() {
+            surface.flush(context)?;
+        }
+
+        for surface in surfaces {
+            if !surface.is_current_draw(context) {
+                context.make_current(surface)?;
+            }
+            surface.swap_buffers(context)?;
+        }
+
+        Ok(())
+    }
+
+    /// Set up the headless rendering in one call, picking the first config
+    /// matching the `template`, creating the pbuffer surface of the given size
+    /// with it and making the default context current on it.
+    ///
+    /// The [`ConfigSurfaceTypes::PBUFFER`] is always added to the surface types
+    /// of the `template`. [`ErrorKind::NotFound`] is returned when no config
+    /// matches it.
+    ///
+    /// ```no_run
+    /// # use std::num::NonZeroU32;
+    /// # use glutin::config::ConfigTemplateBuilder;
+    /// # let gl_display: glutin::display::Display = unimplemented!();
+    /// let size = NonZeroU32::new(256).unwrap();
+    /// let template = ConfigTemplateBuilder::new().build();
+    /// let (gl_config, gl_surface, gl_context) =
+    ///     unsafe { gl_display.create_offscreen(size, size, template).unwrap() };
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`GlDisplay::find_configs`].
+    ///
+    /// [`ConfigSurfaceTypes::PBUFFER`]: crate::config::ConfigSurfaceTypes::PBUFFER
+    pub unsafe fn create_offscreen(
+        &self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        mut template: ConfigTemplate,
+    ) -> Result<(Config, Surface<PbufferSurface>, PossiblyCurrentContext)> {
+        template.config_surface_types |= ConfigSurfaceTypes::PBUFFER;
+        let config = unsafe { self.find_configs(template)? }
+            .next()
+            .ok_or_else(|| Error::from(ErrorKind::NotFound))?;
+
+        let surface_attributes =
+            SurfaceAttributesBuilder::<PbufferSurface>::new().build(width, height);
+        let surface = unsafe { self.create_pbuffer_surface(&config, &surface_attributes)? };
+
+        let context_attributes = ContextAttributesBuilder::new().build(None);
+        let context = unsafe { self.create_context(&config, &context_attributes)? };
+        let context = context.make_current(&surface)?;
+
+        Ok((config, surface, context))
+    }
 }
 
 impl GlDisplay for Display {
@@ -293,25 +674,51 @@ impl GlDisplay for Display {
         config: &Self::Config,
         context_attributes: &ContextAttributes,
     ) -> Result<Self::NotCurrentContext> {
-        match (self, config) {
+        let context = match (self, config) {
             #[cfg(egl_backend)]
             (Self::Egl(display), Config::Egl(config)) => unsafe {
-                Ok(NotCurrentContext::Egl(display.create_context(config, context_attributes)?))
+                display.create_context(config, context_attributes).map(NotCurrentContext::Egl)
             },
             #[cfg(glx_backend)]
             (Self::Glx(display), Config::Glx(config)) => unsafe {
-                Ok(NotCurrentContext::Glx(display.create_context(config, context_attributes)?))
+                display.create_context(config, context_attributes).map(NotCurrentContext::Glx)
             },
             #[cfg(wgl_backend)]
             (Self::Wgl(display), Config::Wgl(config)) => unsafe {
-                Ok(NotCurrentContext::Wgl(display.create_context(config, context_attributes)?))
+                display.create_context(config, context_attributes).map(NotCurrentContext::Wgl)
             },
             #[cfg(cgl_backend)]
             (Self::Cgl(display), Config::Cgl(config)) => unsafe {
-                Ok(NotCurrentContext::Cgl(display.create_context(config, context_attributes)?))
+                display.create_context(config, context_attributes).map(NotCurrentContext::Cgl)
             },
             _ => unreachable!(),
-        }
+        };
+
+        // Describe the request, so a single error message tells why the driver refused
+        // it.
+        context.map_err(|err| {
+            let config = match config {
+                #[cfg(egl_backend)]
+                Config::Egl(config) => format!("EGL config {}", config.config_id()),
+                #[cfg(glx_backend)]
+                Config::Glx(config) => format!("GLX fbconfig {}", config.config_id()),
+                #[cfg(wgl_backend)]
+                Config::Wgl(config) => {
+                    format!("WGL pixel format {}", config.inner.pixel_format_index)
+                },
+                #[cfg(cgl_backend)]
+                Config::Cgl(_) => String::from("CGL pixel format"),
+            };
+            err.with_details(format_args!(
+                "failed to create {:?} context (profile: {:?}, robustness: {:?}, debug: {}) with \
+                 {}",
+                context_attributes.api,
+                context_attributes.profile,
+                context_attributes.robustness,
+                context_attributes.debug,
+                config,
+            ))
+        })
     }
 
     unsafe fn create_window_surface(
@@ -413,6 +820,30 @@ impl AsRawDisplay for Display {
 
 impl Sealed for Display {}
 
+/// The function resolving the OpenGL functions by their name, see
+/// [`Display::set_proc_loader`].
+pub type ProcLoader = Box<dyn Fn(&CStr) -> *const ffi::c_void + Send + Sync>;
+
+/// The storage for the [`ProcLoader`] of the display.
+#[derive(Default)]
+pub(crate) struct ProcLoaderCell(OnceCell<ProcLoader>);
+
+impl ProcLoaderCell {
+    pub(crate) fn set(&self, loader: ProcLoader) -> Result<()> {
+        self.0.set(loader).map_err(|_| ErrorKind::BadAccess.into())
+    }
+
+    pub(crate) fn get(&self) -> Option<&ProcLoader> {
+        self.0.get()
+    }
+}
+
+impl fmt::Debug for ProcLoaderCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProcLoader").field(&self.0.get().is_some()).finish()
+    }
+}
+
 /// Preference of the display that should be used.
 pub enum DisplayApiPreference {
     /// Use only EGL.
@@ -434,6 +865,13 @@ pub enum DisplayApiPreference {
     #[cfg(egl_backend)]
     Egl,
 
+    /// Use only EGL provided by ANGLE, forcing the given backend of it.
+    ///
+    /// The default backend of ANGLE varies between the platforms and its
+    /// versions, so this could be used to require the D3D11 one on Windows.
+    #[cfg(egl_backend)]
+    Angle(AngleBackend),
+
     /// Use only GLX.
     ///
     /// The native GLX platform, it's not very optimal since it's usually tied
@@ -453,7 +891,8 @@ pub enum DisplayApiPreference {
     /// and create the window beforehand.
     ///
     /// When raw window handle isn't provided the display will lack extensions
-    /// support and most features will be lacking.
+    /// support and most features will be lacking. Otherwise that window must
+    /// outlive the display, which holds its device context.
     #[cfg(wgl_backend)]
     Wgl(Option<raw_window_handle::RawWindowHandle>),
 
@@ -505,6 +944,8 @@ impl fmt::Debug for DisplayApiPreference {
         let api = match self {
             #[cfg(egl_backend)]
             DisplayApiPreference::Egl => "Egl",
+            #[cfg(egl_backend)]
+            DisplayApiPreference::Angle(_) => "Angle",
             #[cfg(glx_backend)]
             DisplayApiPreference::Glx(_) => "Glx",
             #[cfg(all(egl_backend, glx_backend))]
@@ -525,6 +966,48 @@ impl fmt::Debug for DisplayApiPreference {
     }
 }
 
+/// The GPU that should be preferred on systems with multiple GPUs.
+///
+/// See [`Display::with_gpu_preference`] for details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GpuPreference {
+    /// Let the system decide.
+    #[default]
+    Default,
+
+    /// Prefer the power efficient GPU, usually the integrated one.
+    LowPower,
+
+    /// Prefer the fast GPU, usually the discrete one.
+    HighPerformance,
+}
+
+/// The backend used by ANGLE to implement OpenGL ES.
+///
+/// See `DisplayApiPreference::Angle`, which is available with the EGL
+/// backend.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AngleBackend {
+    /// Let ANGLE decide.
+    #[default]
+    Default,
+
+    /// Direct3D 9.
+    D3D9,
+
+    /// Direct3D 11.
+    D3D11,
+
+    /// Desktop OpenGL.
+    OpenGl,
+
+    /// Vulkan.
+    Vulkan,
+
+    /// Metal.
+    Metal,
+}
+
 bitflags! {
     /// The features and extensions supported by the [`Display`].
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -571,6 +1054,26 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// The known issues of the driver backing the [`Display`], see
+    /// [`Display::driver_quirks`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DriverQuirks: u32 {
+        /// The NVIDIA driver on Wayland, which crashes on exit unless the EGL
+        /// display is terminated before the Wayland connection is closed.
+        const NVIDIA_WAYLAND_TERMINATE_ON_EXIT = 0b0000_0001;
+
+        /// The Mesa zink driver running on top of the software Vulkan
+        /// implementation, like lavapipe, thus the rendering isn't accelerated
+        /// even though the device isn't reported as the software one.
+        const MESA_ZINK_SOFTWARE               = 0b0000_0010;
+
+        /// ANGLE translating OpenGL ES to Direct3D, where the surfaces could be
+        /// shared with Direct3D, but the desktop OpenGL is not available.
+        const ANGLE_D3D                        = 0b0000_0100;
+    }
+}
+
 /// Raw GL platform display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RawDisplay {
@@ -582,9 +1085,13 @@ pub enum RawDisplay {
     #[cfg(glx_backend)]
     Glx(*const std::ffi::c_void),
 
-    /// Raw display is WGL.
+    /// Raw WGL display.
+    ///
+    /// This is the `HDC` of the window passed to the display on creation or
+    /// null if no window was provided. The `HDC` is owned by the display and
+    /// released when it's dropped.
     #[cfg(wgl_backend)]
-    Wgl,
+    Wgl(*const std::ffi::c_void),
 
     /// Raw display is CGL.
     #[cfg(cgl_backend)]
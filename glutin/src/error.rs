@@ -34,17 +34,43 @@ impl Error {
         matches!(&self.kind, ErrorKind::NotSupported(_))
     }
 
+    /// Helper to check that error is [`ErrorKind::ContextLost`].
+    ///
+    /// The context and all the surfaces made current with it must be recreated
+    /// together with the GL resources, like after a GPU reset.
+    #[inline]
+    pub fn context_lost(&self) -> bool {
+        matches!(&self.kind, ErrorKind::ContextLost)
+    }
+
     /// The underlying error kind.
     #[inline]
     pub fn error_kind(&self) -> ErrorKind {
         self.kind
     }
 
+    /// Helper to check whether the operation could be retried with the same
+    /// resources.
+    ///
+    /// See [`ErrorKind::is_recoverable`] for details.
+    #[inline]
+    pub fn is_recoverable(&self) -> bool {
+        self.kind.is_recoverable()
+    }
+
     /// The underlying raw code in case it's present.
     #[inline]
     pub fn raw_code(&self) -> Option<i64> {
         self.raw_code
     }
+
+    /// Prefix the message of the error with the `details` about the failed
+    /// operation, keeping its code and kind.
+    pub(crate) fn with_details(mut self, details: impl fmt::Display) -> Self {
+        let msg = self.raw_os_message.as_deref().unwrap_or_else(|| self.kind.as_str());
+        self.raw_os_message = Some(format!("{details}: {msg}"));
+        self
+    }
 }
 
 impl fmt::Display for Error {
@@ -127,6 +153,12 @@ pub enum ErrorKind {
     /// One or more argument values are invalid.
     BadParameter,
 
+    /// The requested context attributes can't be combined, like the debug
+    /// flag with [`Robustness::NoError`] on GLX.
+    ///
+    /// [`Robustness::NoError`]: crate::context::Robustness::NoError
+    InvalidContextAttributes(&'static str),
+
     /// Bad native pixmap was provided.
     BadNativePixmap,
 
@@ -144,6 +176,41 @@ pub enum ErrorKind {
 }
 
 impl ErrorKind {
+    /// Whether the resources involved in the failed operation are still valid.
+    ///
+    /// When `false` is returned, like for [`ErrorKind::ContextLost`] or
+    /// [`ErrorKind::BadSurface`], the context and its surfaces must be
+    /// recreated; otherwise the operation could be retried.
+    ///
+    /// ```no_run
+    /// # use glutin::context::PossiblyCurrentContext;
+    /// # use glutin::prelude::*;
+    /// # use glutin::surface::{Surface, WindowSurface};
+    /// # fn swap(surface: &Surface<WindowSurface>, context: &PossiblyCurrentContext) {
+    /// match surface.swap_buffers(context) {
+    ///     Ok(()) => (),
+    ///     Err(err) if err.is_recoverable() => { /* Retry on the next frame. */ },
+    ///     Err(_) => { /* Recreate the context and the surface. */ },
+    /// }
+    /// # }
+    /// ```
+    pub fn is_recoverable(&self) -> bool {
+        use ErrorKind::*;
+        !matches!(
+            self,
+            InitializationFailed
+                | BadContext
+                | BadCurrentSurface
+                | BadDisplay
+                | BadSurface
+                | BadPbuffer
+                | BadPixmap
+                | BadNativePixmap
+                | BadNativeWindow
+                | ContextLost
+        )
+    }
+
     pub(crate) fn as_str(&self) -> &'static str {
         use ErrorKind::*;
         match *self {
@@ -165,6 +232,7 @@ impl ErrorKind {
             BadNativePixmap => "argument does not refer to a valid native pixmap",
             BadNativeWindow => "argument does not refer to a valid native window",
             ContextLost => "context loss",
+            InvalidContextAttributes(reason) => reason,
             NotSupported(reason) => reason,
             Misc => "misc platform error",
         }
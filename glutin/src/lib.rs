@@ -39,6 +39,9 @@ pub mod surface;
 #[cfg(any(egl_backend, glx_backend))]
 mod lib_loading;
 
+#[cfg(any(egl_backend, glx_backend, wgl_backend))]
+mod current_guard;
+
 pub(crate) mod private {
     /// Prevent traits from being implemented downstream, since those are used
     /// purely for documentation organization and simplify platform api
@@ -3,21 +3,48 @@
 use std::mem;
 
 use once_cell::sync::Lazy;
-use x11_dl::xlib::{Display, XVisualInfo, Xlib};
-#[cfg(egl_backend)]
-use x11_dl::xlib::{VisualIDMask, XID};
+use x11_dl::xlib::{Display, VisualIDMask, XVisualInfo, XWindowAttributes, Xlib, XID};
 use x11_dl::xrender::Xrender;
 
+use crate::config::NativeVisual;
+
 /// The XLIB handle.
 pub(crate) static XLIB: Lazy<Option<Xlib>> = Lazy::new(|| Xlib::open().ok());
 
 /// The XRENDER handle.
 static XRENDER: Lazy<Option<Xrender>> = Lazy::new(|| Xrender::open().ok());
 
+/// Query the visual of the `window` with `XGetWindowAttributes`.
+pub(crate) unsafe fn window_visual_id(display: *mut Display, window: XID) -> Option<u32> {
+    let xlib = XLIB.as_ref()?;
+
+    let visual = unsafe {
+        let mut attributes: XWindowAttributes = mem::zeroed();
+        if (xlib.XGetWindowAttributes)(display, window, &mut attributes) == 0 {
+            return None;
+        }
+        attributes.visual
+    };
+
+    if visual.is_null() {
+        None
+    } else {
+        Some(unsafe { (xlib.XVisualIDFromVisual)(visual) } as u32)
+    }
+}
+
 /// The GlConfig extension trait to get X11 specific properties from a config.
 pub trait X11GlConfigExt {
     /// The `X11VisualInfo` that must be used to initialize the Xlib window.
     fn x11_visual(&self) -> Option<X11VisualInfo>;
+
+    /// The `VisualID` the Xlib window must be created with.
+    ///
+    /// Unlike [`Self::x11_visual`], this doesn't query the server for the
+    /// entire visual info.
+    fn x11_visual_id(&self) -> Option<u32> {
+        self.x11_visual().map(|visual| visual.visual_id() as u32)
+    }
 }
 
 /// The X11 visual info.
@@ -27,11 +54,10 @@ pub trait X11GlConfigExt {
 #[derive(Debug)]
 pub struct X11VisualInfo {
     raw: *const XVisualInfo,
-    transparency: bool,
+    alpha_mask: u16,
 }
 
 impl X11VisualInfo {
-    #[cfg(egl_backend)]
     pub(crate) unsafe fn from_xid(display: *mut Display, xid: XID) -> Option<Self> {
         let xlib = XLIB.as_ref().unwrap();
 
@@ -51,20 +77,39 @@ impl X11VisualInfo {
             return None;
         }
 
-        let transparency = Self::has_non_zero_alpha(display, raw);
+        let alpha_mask = Self::alpha_mask_of(display, raw);
 
-        Some(Self { raw, transparency })
+        Some(Self { raw, alpha_mask })
     }
 
     #[cfg(glx_backend)]
     pub(crate) unsafe fn from_raw(display: *mut Display, raw: *const XVisualInfo) -> Self {
-        let transparency = Self::has_non_zero_alpha(display, raw);
-        Self { raw, transparency }
+        let alpha_mask = Self::alpha_mask_of(display, raw);
+        Self { raw, alpha_mask }
     }
 
     /// Returns `true` if the visual has non-zero alpha mask.
     pub fn supports_transparency(&self) -> bool {
-        self.transparency
+        self.alpha_mask != 0
+    }
+
+    /// The alpha mask of the visual.
+    pub fn alpha_mask(&self) -> u16 {
+        self.alpha_mask
+    }
+
+    /// The depth of the visual.
+    pub fn depth(&self) -> u8 {
+        unsafe { (*self.raw).depth as u8 }
+    }
+
+    /// The visual as a [`NativeVisual`].
+    pub(crate) fn native_visual(&self) -> NativeVisual {
+        NativeVisual::X11 {
+            visual_id: self.visual_id(),
+            depth: self.depth(),
+            alpha_mask: self.alpha_mask,
+        }
     }
 
     /// Get XID of for this visual.
@@ -81,14 +126,16 @@ impl X11VisualInfo {
         raw
     }
 
-    pub(crate) fn has_non_zero_alpha(display: *mut Display, raw: *const XVisualInfo) -> bool {
+    fn alpha_mask_of(display: *mut Display, raw: *const XVisualInfo) -> u16 {
         let xrender = XRENDER.as_ref().unwrap();
         unsafe {
             let visual_format = (xrender.XRenderFindVisualFormat)(display, (*raw).visual);
 
-            (!visual_format.is_null())
-                .then(|| (*visual_format).direct.alphaMask != 0)
-                .unwrap_or(false)
+            if visual_format.is_null() {
+                0
+            } else {
+                (*visual_format).direct.alphaMask as u16
+            }
         }
     }
 }
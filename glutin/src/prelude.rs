@@ -8,6 +8,33 @@
 //! # #![allow(unused_imports)]
 //! use glutin::prelude::*;
 //! ```
+//!
+//! Importing the prelude is enough to use the surfaces generically, since all
+//! their methods are provided by the [`GlSurface`] trait.
+//!
+//! ```no_run
+//! use std::num::NonZeroU32;
+//!
+//! use glutin::error::Result;
+//! use glutin::prelude::*;
+//! use glutin::surface::{SwapInterval, WindowSurface};
+//!
+//! fn present<S>(surface: &S, context: &S::Context, width: u32, height: u32) -> Result<()>
+//! where
+//!     S: GlSurface<WindowSurface, SurfaceType = WindowSurface>,
+//! {
+//!     if !surface.is_current(context) {
+//!         return Ok(());
+//!     }
+//!
+//!     if let (Some(width), Some(height)) = (NonZeroU32::new(width), NonZeroU32::new(height)) {
+//!         surface.resize(context, width, height);
+//!     }
+//!
+//!     surface.set_swap_interval(context, SwapInterval::Wait(NonZeroU32::MIN))?;
+//!     surface.swap_buffers(context)
+//! }
+//! ```
 
 pub use crate::config::GlConfig;
 pub use crate::context::{GlContext, NotCurrentGlContext, PossiblyCurrentGlContext};
@@ -1,14 +1,16 @@
 //! A cross platform OpenGL surface representation.
 #![allow(unreachable_patterns)]
 
+use std::ffi::CStr;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+use std::{fmt, mem};
 
 use raw_window_handle::RawWindowHandle;
 
 use crate::context::{PossiblyCurrentContext, PossiblyCurrentGlContext};
-use crate::display::{Display, GetGlDisplay};
-use crate::error::Result;
+use crate::display::{Display, GetGlDisplay, GlDisplay};
+use crate::error::{Error, ErrorKind, Result};
 use crate::private::{gl_api_dispatch, Sealed};
 
 #[cfg(cgl_backend)]
@@ -39,12 +41,26 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
     fn buffer_age(&self) -> u32;
 
     /// The **physical** width of the underlying surface.
+    ///
+    /// `None` is returned when the size couldn't be queried.
+    ///
+    /// # Platform specific
+    ///
+    /// - **EGL:** queried with `EGL_WIDTH` via `eglQuerySurface`.
+    /// - **GLX:** queried with `GLX_WIDTH` via `glXQueryDrawable`.
+    /// - **WGL:** the width of the window client area.
+    /// - **macOS: this will block if your main thread is blocked.**
     fn width(&self) -> Option<u32>;
 
     /// The **physical** height of the underlying surface.
     ///
+    /// `None` is returned when the size couldn't be queried.
+    ///
     /// # Platform specific
     ///
+    /// - **EGL:** queried with `EGL_HEIGHT` via `eglQuerySurface`.
+    /// - **GLX:** queried with `GLX_HEIGHT` via `glXQueryDrawable`.
+    /// - **WGL:** the height of the window client area.
     /// - **macOS: this will block if your main thread is blocked.**
     fn height(&self) -> Option<u32>;
 
@@ -55,10 +71,63 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
     /// - **macOS: this will block if your main thread is blocked.**
     fn is_single_buffered(&self) -> bool;
 
+    /// The origin of the default framebuffer of the surface.
+    ///
+    /// # Platform specific
+    ///
+    /// - **EGL:** queried with `EGL_SURFACE_ORIENTATION_ANGLE` when
+    ///   `EGL_ANGLE_surface_orientation` is supported,
+    ///   [`SurfaceOrigin::BottomLeft`] otherwise.
+    /// - **GLX/WGL/CGL:** always [`SurfaceOrigin::BottomLeft`].
+    fn origin(&self) -> SurfaceOrigin;
+
     /// Swaps the underlying back buffers when the surface is not single
     /// buffered.
+    ///
+    /// [`ErrorKind::ContextLost`] is returned when the context was lost, in
+    /// which case the context and the surface must be recreated.
+    ///
+    /// The swap always performs the implicit flush of the `context`, as
+    /// mandated by `eglSwapBuffers`, `glXSwapBuffers`, `SwapBuffers` and
+    /// `CGLFlushDrawable`, thus there's no way to skip it even when the
+    /// application manages its own fences. Only the flush on context release
+    /// could be avoided, see [`ReleaseBehavior::None`].
+    ///
+    /// [`ReleaseBehavior::None`]: crate::context::ReleaseBehavior::None
     fn swap_buffers(&self, context: &Self::Context) -> Result<()>;
 
+    /// Call `glFlush` on the `context`, which must be current to the calling
+    /// thread.
+    ///
+    /// The function pointer is resolved on the first call and reused
+    /// afterwards.
+    fn flush(&self, context: &Self::Context) -> Result<()>;
+
+    /// Call `glFinish` on the `context`, which must be current to the calling
+    /// thread, blocking until all the issued commands are completed.
+    ///
+    /// The function pointer is resolved on the first call and reused
+    /// afterwards.
+    fn finish(&self, context: &Self::Context) -> Result<()>;
+
+    /// Read the pixels of the `rect` from the surface, which must be current
+    /// to the `context` with the default framebuffer bound for reading.
+    ///
+    /// The back buffer is read when the surface is double buffered, so it
+    /// should be called before [`Self::swap_buffers`], while the front buffer
+    /// is read for the single buffered surfaces. The rows are tightly packed
+    /// and go from the bottom of the `rect` to its top. The `GL_READ_BUFFER`
+    /// and `GL_PACK_ALIGNMENT` are restored afterwards.
+    ///
+    /// [`ErrorKind::BadParameter`] is returned when the `rect` is empty or has
+    /// a negative origin.
+    fn read_pixels(
+        &self,
+        context: &Self::Context,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>>;
+
     /// Check whether the surface is current on to the current thread.
     fn is_current(&self, context: &Self::Context) -> bool;
 
@@ -72,6 +141,11 @@ pub trait GlSurface<T: SurfaceTypeTrait>: Sealed {
 
     /// Set swap interval for the surface.
     ///
+    /// The `context` must be current on the calling thread with this surface
+    /// as its draw surface, otherwise [`ErrorKind::BadContextState`] is
+    /// returned on EGL, where the interval is applied to whatever surface is
+    /// current.
+    ///
     /// See [`crate::surface::SwapInterval`] for details.
     fn set_swap_interval(&self, context: &Self::Context, interval: SwapInterval) -> Result<()>;
 
@@ -103,7 +177,10 @@ pub trait ResizeableSurface: Sealed {}
 
 /// Trait for accessing the raw GL surface.
 pub trait AsRawSurface {
-    /// Get the raw handle to the surface.
+    /// Get the raw handle to the surface, e.g. to pass it to the external
+    /// tools, like frame capture ones, attaching to the native surface.
+    ///
+    /// The handle is only valid while the surface is alive.
     fn raw_surface(&self) -> RawSurface;
 }
 
@@ -130,6 +207,23 @@ impl<T: SurfaceTypeTrait + Default> SurfaceAttributesBuilder<T> {
         self.attributes.srgb = srgb;
         self
     }
+
+    /// Require the buffers backing the surface to use the given DRM format
+    /// modifier. Passing `None` means you don't care.
+    ///
+    /// The surface creation fails with [`ErrorKind::BadMatch`] when the
+    /// modifier is not among the ones supported by the config.
+    ///
+    /// # Api-specific.
+    ///
+    /// This only controls EGL surfaces and is checked against the EGL
+    /// `Config::drm_format_modifiers`. The native
+    /// buffers, like the GBM surface, must be allocated with that modifier by
+    /// the caller.
+    pub fn with_drm_format_modifier(mut self, modifier: Option<u64>) -> Self {
+        self.attributes.drm_format_modifier = modifier;
+        self
+    }
 }
 
 impl SurfaceAttributesBuilder<WindowSurface> {
@@ -149,6 +243,21 @@ impl SurfaceAttributesBuilder<WindowSurface> {
     }
 
     /// Build the surface attributes suitable to create a window surface.
+    ///
+    /// The `width` and `height` don't have to match the size of the window,
+    /// e.g. for tiled rendering, however most platforms derive the size of
+    /// the surface from the native window and ignore them.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Wayland:** the size is used to create the `wl_egl_window`, so it's
+    ///   honored until the next [`GlSurface::resize`].
+    /// - **X11 / Windows / macOS / Android:** the size of the native window is
+    ///   always used.
+    ///
+    /// The `raw_window_handle` could come from any windowing library, like
+    /// SDL2, glutin doesn't depend on winit, see the `raw_window_handle`
+    /// example.
     pub fn build(
         mut self,
         raw_window_handle: RawWindowHandle,
@@ -164,11 +273,65 @@ impl SurfaceAttributesBuilder<WindowSurface> {
 
 impl SurfaceAttributesBuilder<PbufferSurface> {
     /// Request the largest pbuffer.
+    ///
+    /// When the requested size isn't available, the largest available one is
+    /// used instead of failing. The granted size could be queried with
+    /// [`GlSurface::width`] and [`GlSurface::height`].
+    ///
+    /// The default is `false`.
+    ///
+    /// # Api-specific
+    ///
+    /// - **EGL:** sets `EGL_LARGEST_PBUFFER`.
+    /// - **GLX:** sets `GLX_LARGEST_PBUFFER`.
     pub fn with_largest_pbuffer(mut self, largest_pbuffer: bool) -> Self {
         self.attributes.largest_pbuffer = largest_pbuffer;
         self
     }
 
+    /// The texture target the pbuffer could be bound to, with `None` meaning
+    /// that it couldn't be bound as a texture.
+    ///
+    /// The default is `None`.
+    ///
+    /// # Api-specific
+    ///
+    /// This only controls EGL surfaces, where it sets `EGL_TEXTURE_TARGET`.
+    /// The texture format must be set as well, see
+    /// [`Self::with_texture_format`].
+    pub fn with_texture_target(mut self, texture_target: Option<TextureTarget>) -> Self {
+        self.attributes.texture_target = texture_target;
+        self
+    }
+
+    /// The format of the texture the pbuffer could be bound to, with `None`
+    /// meaning that it couldn't be bound as a texture.
+    ///
+    /// The default is `None`.
+    ///
+    /// # Api-specific
+    ///
+    /// This only controls EGL surfaces, where it sets `EGL_TEXTURE_FORMAT`.
+    /// The config must have `EGL_BIND_TO_TEXTURE_RGB` or
+    /// `EGL_BIND_TO_TEXTURE_RGBA` set for the requested format.
+    pub fn with_texture_format(mut self, texture_format: Option<TextureFormat>) -> Self {
+        self.attributes.texture_format = texture_format;
+        self
+    }
+
+    /// Allocate the space for the mipmaps of the texture the pbuffer is bound
+    /// to.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Api-specific
+    ///
+    /// This only controls EGL surfaces, where it sets `EGL_MIPMAP_TEXTURE`.
+    pub fn with_mipmaps(mut self, mipmaps: bool) -> Self {
+        self.attributes.mipmaps = mipmaps;
+        self
+    }
+
     /// The same as in
     /// [`SurfaceAttributesBuilder::<WindowSurface>::with_single_buffer`].
     pub fn with_single_buffer(mut self, single_buffer: bool) -> Self {
@@ -206,6 +369,10 @@ pub struct SurfaceAttributes<T: SurfaceTypeTrait> {
     pub(crate) largest_pbuffer: bool,
     pub(crate) raw_window_handle: Option<RawWindowHandle>,
     pub(crate) native_pixmap: Option<NativePixmap>,
+    pub(crate) drm_format_modifier: Option<u64>,
+    pub(crate) texture_target: Option<TextureTarget>,
+    pub(crate) texture_format: Option<TextureFormat>,
+    pub(crate) mipmaps: bool,
     _ty: PhantomData<T>,
 }
 
@@ -313,6 +480,10 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         gl_api_dispatch!(self; Self(surface) => surface.is_single_buffered())
     }
 
+    fn origin(&self) -> SurfaceOrigin {
+        gl_api_dispatch!(self; Self(surface) => surface.origin())
+    }
+
     fn swap_buffers(&self, context: &Self::Context) -> Result<()> {
         match (self, context) {
             #[cfg(egl_backend)]
@@ -357,6 +528,61 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
         }
     }
 
+    fn flush(&self, context: &Self::Context) -> Result<()> {
+        match (self, context) {
+            #[cfg(egl_backend)]
+            (Self::Egl(surface), PossiblyCurrentContext::Egl(context)) => surface.flush(context),
+            #[cfg(glx_backend)]
+            (Self::Glx(surface), PossiblyCurrentContext::Glx(context)) => surface.flush(context),
+            #[cfg(cgl_backend)]
+            (Self::Cgl(surface), PossiblyCurrentContext::Cgl(context)) => surface.flush(context),
+            #[cfg(wgl_backend)]
+            (Self::Wgl(surface), PossiblyCurrentContext::Wgl(context)) => surface.flush(context),
+            _ => unreachable!(),
+        }
+    }
+
+    fn finish(&self, context: &Self::Context) -> Result<()> {
+        match (self, context) {
+            #[cfg(egl_backend)]
+            (Self::Egl(surface), PossiblyCurrentContext::Egl(context)) => surface.finish(context),
+            #[cfg(glx_backend)]
+            (Self::Glx(surface), PossiblyCurrentContext::Glx(context)) => surface.finish(context),
+            #[cfg(cgl_backend)]
+            (Self::Cgl(surface), PossiblyCurrentContext::Cgl(context)) => surface.finish(context),
+            #[cfg(wgl_backend)]
+            (Self::Wgl(surface), PossiblyCurrentContext::Wgl(context)) => surface.finish(context),
+            _ => unreachable!(),
+        }
+    }
+
+    fn read_pixels(
+        &self,
+        context: &Self::Context,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>> {
+        match (self, context) {
+            #[cfg(egl_backend)]
+            (Self::Egl(surface), PossiblyCurrentContext::Egl(context)) => {
+                surface.read_pixels(context, rect, format)
+            },
+            #[cfg(glx_backend)]
+            (Self::Glx(surface), PossiblyCurrentContext::Glx(context)) => {
+                surface.read_pixels(context, rect, format)
+            },
+            #[cfg(cgl_backend)]
+            (Self::Cgl(surface), PossiblyCurrentContext::Cgl(context)) => {
+                surface.read_pixels(context, rect, format)
+            },
+            #[cfg(wgl_backend)]
+            (Self::Wgl(surface), PossiblyCurrentContext::Wgl(context)) => {
+                surface.read_pixels(context, rect, format)
+            },
+            _ => unreachable!(),
+        }
+    }
+
     fn is_current(&self, context: &Self::Context) -> bool {
         match (self, context) {
             #[cfg(egl_backend)]
@@ -449,6 +675,31 @@ impl<T: SurfaceTypeTrait> GlSurface<T> for Surface<T> {
     }
 }
 
+impl Surface<WindowSurface> {
+    /// Map the front buffer of the surface for reading it on the CPU without
+    /// going through GL, like for the screen capture.
+    ///
+    /// The buffer presented by the last [`GlSurface::swap_buffers`] is locked
+    /// until the returned [`MappedBuffer`] is dropped, so it must not be
+    /// locked by the application in the meantime, and must be dropped before
+    /// the next swap.
+    ///
+    /// [`None`] is returned when the front buffer can't be mapped.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **GBM:** locks the buffer with `gbm_surface_lock_front_buffer` and
+    ///   maps it with `gbm_bo_map`.
+    /// - **Other platforms:** always [`None`].
+    pub fn map_front_buffer(&self) -> Option<MappedBuffer<'_>> {
+        match self {
+            #[cfg(all(egl_backend, free_unix))]
+            Self::Egl(surface) => surface.map_front_buffer(),
+            _ => None,
+        }
+    }
+}
+
 impl<T: SurfaceTypeTrait> GetGlDisplay for Surface<T> {
     type Target = Display;
 
@@ -482,7 +733,14 @@ impl<T: SurfaceTypeTrait> Sealed for Surface<T> {}
 ///   the window is visible again. Using this variant is not recommended on
 ///   Wayland and instead the throttling should be performed by [`frame
 ///   callbacks`].
+/// - **macOS:** the interval is set on the context rather than the surface, and
+///   any [`SwapInterval::Wait`] value is clamped to `1`, so `Wait(2)` behaves
+///   exactly like `Wait(1)`.
+/// - **EGL:** the interval outside of the range of the surface's config, see
+///   `Config::min_swap_interval` and `Config::max_swap_interval`, is rejected
+///   with [`ErrorKind::BadParameter`].
 ///
+/// [`ErrorKind::BadParameter`]: crate::error::ErrorKind::BadParameter
 /// [`frame callbacks`]: https://wayland.freedesktop.org/docs/html/apa.html#protocol-spec-wl_surface-request-frame
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SwapInterval {
@@ -495,6 +753,35 @@ pub enum SwapInterval {
     Wait(NonZeroU32),
 }
 
+/// The origin convention of the surface, which affects the rendering and the
+/// reading of its pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SurfaceOrigin {
+    /// The origin is in the bottom left corner, like OpenGL mandates.
+    #[default]
+    BottomLeft,
+
+    /// The origin is in the top left corner, so the surface is Y-flipped.
+    TopLeft,
+}
+
+/// The texture target a pbuffer could be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureTarget {
+    /// `GL_TEXTURE_2D`.
+    Texture2D,
+}
+
+/// The format of the texture a pbuffer could be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureFormat {
+    /// RGB texture.
+    Rgb,
+
+    /// RGBA texture.
+    Rgba,
+}
+
 /// A platform native pixmap.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NativePixmap {
@@ -506,6 +793,11 @@ pub enum NativePixmap {
 
     /// HBITMAP handle for windows bitmap.
     WindowsPixmap(isize),
+
+    /// Pointer to the `gbm_bo` from GBM.
+    ///
+    /// Requires the EGL display created for the GBM device.
+    GbmBo(std::ptr::NonNull<std::ffi::c_void>),
 }
 
 /// Handle to the raw OpenGL surface.
@@ -519,7 +811,10 @@ pub enum RawSurface {
     #[cfg(glx_backend)]
     Glx(u64),
 
-    /// HWND
+    /// HWND of the window.
+    ///
+    /// Its `HDC` could be obtained with
+    /// [`crate::api::wgl::surface::Surface::raw_device_context`].
     #[cfg(wgl_backend)]
     Wgl(*const std::ffi::c_void),
 
@@ -528,6 +823,69 @@ pub enum RawSurface {
     Cgl(*const std::ffi::c_void),
 }
 
+/// The front buffer of the surface mapped for the CPU access, see
+/// [`Surface::map_front_buffer`].
+///
+/// The buffer is unmapped and released back to the surface when dropped.
+pub struct MappedBuffer<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: u32,
+    format: u32,
+    _guard: Box<dyn std::any::Any>,
+}
+
+impl<'a> MappedBuffer<'a> {
+    #[allow(dead_code)]
+    pub(crate) fn new(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: u32,
+        format: u32,
+        guard: Box<dyn std::any::Any>,
+    ) -> Self {
+        Self { data, width, height, stride, format, _guard: guard }
+    }
+
+    /// The pixels of the buffer, [`Self::stride`] bytes per row.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// The width of the buffer in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the buffer in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of bytes between the starts of the consecutive rows.
+    pub fn stride(&self) -> u32 {
+        self.stride
+    }
+
+    /// The fourcc code of the pixel format, like `DRM_FORMAT_XRGB8888`.
+    pub fn format(&self) -> u32 {
+        self.format
+    }
+}
+
+impl fmt::Debug for MappedBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedBuffer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("stride", &self.stride)
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
 /// The rect that is being used in various surface operations.
 ///
 /// The origin is in the bottom left of the surface.
@@ -550,3 +908,174 @@ impl Rect {
         Self { x, y, width, height }
     }
 }
+
+/// The format of the pixels returned by [`GlSurface::read_pixels`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadPixelsFormat {
+    /// `GL_RGBA` with `GL_UNSIGNED_BYTE` components.
+    Rgba8,
+
+    /// `GL_RGB` with `GL_UNSIGNED_BYTE` components.
+    ///
+    /// This format is not guaranteed to be supported on OpenGL ES.
+    Rgb8,
+}
+
+impl ReadPixelsFormat {
+    /// The number of bytes used by a single pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgba8 => 4,
+            Self::Rgb8 => 3,
+        }
+    }
+
+    fn gl_format(self) -> u32 {
+        match self {
+            Self::Rgba8 => GL_RGBA,
+            Self::Rgb8 => GL_RGB,
+        }
+    }
+}
+
+const GL_FRONT: u32 = 0x0404;
+const GL_BACK: u32 = 0x0405;
+const GL_READ_BUFFER: u32 = 0x0C02;
+const GL_PACK_ALIGNMENT: u32 = 0x0D05;
+const GL_UNSIGNED_BYTE: u32 = 0x1401;
+const GL_RGB: u32 = 0x1907;
+const GL_RGBA: u32 = 0x1908;
+
+/// GL functions used by the surface helpers.
+///
+/// The functions are resolved with [`GlDisplay::get_proc_address`] on every
+/// call, since on WGL they depend on the context current at the moment.
+pub(crate) struct GlFunctions;
+
+type GlVoidFn = extern "system" fn();
+
+/// Functions required by [`GlFunctions::read_pixels`].
+#[derive(Debug, Clone, Copy)]
+struct ReadPixelsFns {
+    read_pixels: extern "system" fn(i32, i32, i32, i32, u32, u32, *mut std::ffi::c_void),
+    pixel_store_i: extern "system" fn(u32, i32),
+    get_integer_v: extern "system" fn(u32, *mut i32),
+    get_error: extern "system" fn() -> u32,
+    /// Not available on OpenGL ES 2.0, where only the color buffer could be
+    /// read.
+    read_buffer: Option<extern "system" fn(u32)>,
+}
+
+impl GlFunctions {
+    pub(crate) fn flush(
+        display: &impl GlDisplay,
+        context: &impl PossiblyCurrentGlContext,
+    ) -> Result<()> {
+        Self::call(display, context, b"glFlush\0")
+    }
+
+    pub(crate) fn finish(
+        display: &impl GlDisplay,
+        context: &impl PossiblyCurrentGlContext,
+    ) -> Result<()> {
+        Self::call(display, context, b"glFinish\0")
+    }
+
+    pub(crate) fn read_pixels(
+        display: &impl GlDisplay,
+        context: &impl PossiblyCurrentGlContext,
+        single_buffered: bool,
+        rect: Rect,
+        format: ReadPixelsFormat,
+    ) -> Result<Vec<u8>> {
+        if !context.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        if rect.x < 0 || rect.y < 0 || rect.width <= 0 || rect.height <= 0 {
+            return Err(ErrorKind::BadParameter.into());
+        }
+
+        let load = || unsafe {
+            Some(ReadPixelsFns {
+                read_pixels: Self::load(display, b"glReadPixels\0")?,
+                pixel_store_i: Self::load(display, b"glPixelStorei\0")?,
+                get_integer_v: Self::load(display, b"glGetIntegerv\0")?,
+                get_error: Self::load(display, b"glGetError\0")?,
+                read_buffer: Self::load(display, b"glReadBuffer\0"),
+            })
+        };
+        let fns = load().ok_or(ErrorKind::NotFound)?;
+
+        let len = rect.width as usize * rect.height as usize * format.bytes_per_pixel();
+        let mut pixels = vec![0u8; len];
+
+        // Clear the errors left by the user.
+        while (fns.get_error)() != 0 {}
+
+        let mut pack_alignment = 4;
+        (fns.get_integer_v)(GL_PACK_ALIGNMENT, &mut pack_alignment);
+        (fns.pixel_store_i)(GL_PACK_ALIGNMENT, 1);
+
+        // Without the back buffer, the rendering goes straight to the front one.
+        let mut old_read_buffer = 0;
+        if let Some(read_buffer) = fns.read_buffer {
+            (fns.get_integer_v)(GL_READ_BUFFER, &mut old_read_buffer);
+            read_buffer(if single_buffered { GL_FRONT } else { GL_BACK });
+        }
+
+        (fns.read_pixels)(
+            rect.x,
+            rect.y,
+            rect.width,
+            rect.height,
+            format.gl_format(),
+            GL_UNSIGNED_BYTE,
+            pixels.as_mut_ptr().cast(),
+        );
+        let error = (fns.get_error)();
+
+        (fns.pixel_store_i)(GL_PACK_ALIGNMENT, pack_alignment);
+        if let Some(read_buffer) = fns.read_buffer {
+            read_buffer(old_read_buffer as u32);
+        }
+
+        if error != 0 {
+            return Err(Error::new(
+                Some(error as i64),
+                Some(String::from("glReadPixels failed")),
+                ErrorKind::BadMatch,
+            ));
+        }
+
+        Ok(pixels)
+    }
+
+    fn call(
+        display: &impl GlDisplay,
+        context: &impl PossiblyCurrentGlContext,
+        name: &'static [u8],
+    ) -> Result<()> {
+        if !context.is_current() {
+            return Err(ErrorKind::BadContextState.into());
+        }
+
+        match unsafe { Self::load::<GlVoidFn>(display, name) } {
+            Some(func) => {
+                func();
+                Ok(())
+            },
+            None => Err(ErrorKind::NotFound.into()),
+        }
+    }
+
+    /// Load the function `name`, which must be of type `F`.
+    unsafe fn load<F: Copy>(display: &impl GlDisplay, name: &'static [u8]) -> Option<F> {
+        let func = display.get_proc_address(CStr::from_bytes_with_nul(name).unwrap());
+        if func.is_null() {
+            None
+        } else {
+            Some(unsafe { mem::transmute_copy::<*const std::ffi::c_void, F>(&func) })
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! The helpers shared by the EGL tests.
+//!
+//! The tests need the EGL display created from the device, like the Mesa
+//! software one, and are skipped when there's none, e.g. on the CI machines
+//! without the EGL implementation.
+
+#![allow(dead_code)]
+
+use glutin::api::egl::config::Config;
+use glutin::api::egl::context::PossiblyCurrentContext;
+use glutin::api::egl::device::Device;
+use glutin::api::egl::display::Display;
+use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::context::{ContextApi, ContextAttributes, ContextAttributesBuilder};
+use glutin::prelude::*;
+
+/// The displays of all the devices which could be initialized.
+pub fn device_displays() -> impl Iterator<Item = Display> {
+    Device::query_devices()
+        .into_iter()
+        .flatten()
+        .filter_map(|device| unsafe { Display::with_device(&device, None).ok() })
+}
+
+/// The attributes of the GLES context of unspecified version.
+pub fn gles_attributes() -> ContextAttributes {
+    ContextAttributesBuilder::new().with_context_api(ContextApi::Gles(None)).build(None)
+}
+
+/// Create the context with the given `attributes` current on the calling
+/// thread, surfaceless, on the first device which supports that.
+pub fn current_context(
+    attributes: &ContextAttributes,
+) -> Option<(Display, Config, PossiblyCurrentContext)> {
+    let template = ConfigTemplateBuilder::new().with_surface_type(ConfigSurfaceTypes::empty());
+    device_displays().find_map(|display| unsafe {
+        let config = display.find_configs(template.clone().build()).ok()?.next()?;
+        let context = display.create_context(&config, attributes).ok()?;
+        let context = context.make_current_surfaceless().ok()?;
+        Some((display, config, context))
+    })
+}
@@ -0,0 +1,38 @@
+//! Tests of the tracking of the threads the contexts are current on.
+
+#![cfg(all(egl_backend, free_unix, debug_assertions))]
+
+mod common;
+
+use std::thread;
+
+use glutin::context::{AsRawContext, RawContext};
+use glutin::prelude::*;
+
+#[test]
+fn making_current_on_other_thread_panics_without_poisoning() {
+    let Some((display, config, context)) = common::current_context(&common::gles_attributes())
+    else {
+        eprintln!("skipping, no EGL device supports the surfaceless GLES context");
+        return;
+    };
+
+    let raw = match context.raw_context() {
+        RawContext::Egl(raw) => raw as usize,
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    };
+
+    let panic = thread::spawn(move || unsafe {
+        let context = display.context_from_raw(&config, raw as _).unwrap();
+        let _ = context.make_current_surfaceless();
+    })
+    .join()
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("while still being current"), "{message}");
+
+    // The tracking keeps working after the panic.
+    context.make_current_surfaceless().unwrap();
+    drop(context.make_not_current().unwrap());
+}
@@ -0,0 +1,30 @@
+//! Tests of the EGL configs.
+
+#![cfg(all(egl_backend, free_unix))]
+
+mod common;
+
+use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::prelude::*;
+
+#[test]
+fn combined_surface_types_are_all_supported() {
+    let surface_types = ConfigSurfaceTypes::WINDOW | ConfigSurfaceTypes::PBUFFER;
+    let template = ConfigTemplateBuilder::new().with_surface_type(surface_types).build();
+
+    let mut num_configs = 0;
+    for display in common::device_displays() {
+        let Ok(configs) = (unsafe { display.find_configs(template.clone()) }) else {
+            continue;
+        };
+
+        for config in configs {
+            assert!(config.config_surface_types().contains(surface_types));
+            num_configs += 1;
+        }
+    }
+
+    if num_configs == 0 {
+        eprintln!("skipping, no EGL device has the configs supporting windows and pbuffers");
+    }
+}
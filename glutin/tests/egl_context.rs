@@ -0,0 +1,118 @@
+//! Tests of the EGL contexts.
+
+#![cfg(all(egl_backend, free_unix))]
+
+mod common;
+
+use std::ffi::{c_char, c_void, CStr};
+use std::mem;
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::context::{
+    ContextApi, ContextAttributesBuilder, ContextFlags, ReleaseBehavior, Robustness, Version,
+};
+use glutin::error::ErrorKind;
+use glutin::prelude::*;
+use glutin::surface::{PbufferSurface, SurfaceAttributesBuilder};
+
+const GL_VERSION: u32 = 0x1F02;
+const GL_CONTEXT_RELEASE_BEHAVIOR: u32 = 0x82FB;
+
+type GetString = extern "system" fn(u32) -> *const c_char;
+type GetIntegerv = extern "system" fn(u32, *mut i32);
+
+#[test]
+fn requested_gles_version_is_reported() {
+    let attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::Gles(Some(Version::new(3, 2))))
+        .build(None);
+    let Some((display, _, context)) = common::current_context(&attributes) else {
+        eprintln!("skipping, no EGL device supports the surfaceless GLES 3.2 context");
+        return;
+    };
+
+    let get_string = display.get_proc_address(CStr::from_bytes_with_nul(b"glGetString\0").unwrap());
+    assert!(!get_string.is_null());
+    let get_string = unsafe { mem::transmute::<*const c_void, GetString>(get_string) };
+    let version = unsafe { CStr::from_ptr(get_string(GL_VERSION)) }.to_str().unwrap();
+
+    assert!(version.starts_with("OpenGL ES 3.2"), "{version}");
+    assert_eq!(context.context_api(), ContextApi::Gles(Some(Version::new(3, 2))));
+}
+
+#[test]
+fn release_behavior_none_doesnt_flush_on_make_not_current() {
+    // The context creation fails without `EGL_KHR_context_flush_control`, so
+    // only the devices supporting it are used.
+    let attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::Gles(None))
+        .with_release_behavior(ReleaseBehavior::None)
+        .build(None);
+    let Some((display, _, context)) = common::current_context(&attributes) else {
+        eprintln!("skipping, no EGL device supports EGL_KHR_context_flush_control");
+        return;
+    };
+
+    let get_integerv =
+        display.get_proc_address(CStr::from_bytes_with_nul(b"glGetIntegerv\0").unwrap());
+    assert!(!get_integerv.is_null());
+    let get_integerv = unsafe { mem::transmute::<*const c_void, GetIntegerv>(get_integerv) };
+
+    // The `GL_NONE` behavior means that releasing the context doesn't flush.
+    let mut release_behavior = -1;
+    get_integerv(GL_CONTEXT_RELEASE_BEHAVIOR, &mut release_behavior);
+    assert_eq!(release_behavior, 0);
+
+    let start = Instant::now();
+    let context = context.make_not_current().unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    drop(context);
+}
+
+#[test]
+fn context_created_before_surface_is_made_current_with_it() {
+    let template =
+        ConfigTemplateBuilder::new().with_surface_type(ConfigSurfaceTypes::PBUFFER).build();
+    let Some((display, config)) = common::device_displays().find_map(|display| {
+        let config = unsafe { display.find_configs(template.clone()) }.ok()?.next()?;
+        Some((display, config))
+    }) else {
+        eprintln!("skipping, no EGL device supports the pbuffer configs");
+        return;
+    };
+
+    // The context doesn't need the surface, nor the window, to be created.
+    let context = unsafe { display.create_context(&config, &common::gles_attributes()) }.unwrap();
+
+    let size = NonZeroU32::new(16).unwrap();
+    let attributes = SurfaceAttributesBuilder::<PbufferSurface>::new().build(size, size);
+    let surface = unsafe { display.create_pbuffer_surface(&config, &attributes) }.unwrap();
+
+    let context = context.make_current(&surface).unwrap();
+    assert!(context.is_current());
+    assert!(surface.is_current(&context));
+}
+
+#[test]
+fn robust_and_no_error_flags_are_rejected() {
+    let template = ConfigTemplateBuilder::new().with_surface_type(ConfigSurfaceTypes::empty());
+    let Some((display, config)) = common::device_displays().find_map(|display| {
+        let config = unsafe { display.find_configs(template.clone().build()) }.ok()?.next()?;
+        Some((display, config))
+    }) else {
+        eprintln!("skipping, no EGL device is available");
+        return;
+    };
+
+    let builder = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::Gles(None))
+        .with_flags(ContextFlags::ROBUST | ContextFlags::NO_ERROR);
+    let err = unsafe { display.create_context(&config, &builder.clone().build(None)) }.unwrap_err();
+    assert!(matches!(err.error_kind(), ErrorKind::InvalidContextAttributes(_)), "{err}");
+
+    // The explicit robustness overrides the flags.
+    let attributes = builder.with_robustness(Robustness::NotRobust).build(None);
+    assert!(unsafe { display.create_context(&config, &attributes) }.is_ok());
+}
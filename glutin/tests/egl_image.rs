@@ -0,0 +1,117 @@
+//! Tests of the `EglImage`.
+
+#![cfg(all(egl_backend, free_unix))]
+
+mod common;
+
+use std::ffi::{c_void, CStr};
+use std::mem;
+
+use glutin::api::egl::display::Display;
+use glutin::api::egl::image::GlImageSource;
+use glutin::prelude::*;
+use libloading::Library;
+
+const EGL_SUCCESS: i32 = 0x3000;
+
+const GL_NO_ERROR: u32 = 0;
+const GL_RENDERBUFFER: u32 = 0x8D41;
+const GL_RGBA8: u32 = 0x8058;
+
+type GenRenderbuffers = extern "system" fn(i32, *mut u32);
+type BindRenderbuffer = extern "system" fn(u32, u32);
+type RenderbufferStorage = extern "system" fn(u32, u32, i32, i32);
+type ImageTargetRenderbufferStorage = extern "system" fn(u32, *const c_void);
+type GetError = extern "system" fn() -> u32;
+type EglGetError = extern "system" fn() -> i32;
+
+/// The GL functions used by the tests.
+struct Gl {
+    gen_renderbuffers: GenRenderbuffers,
+    bind_renderbuffer: BindRenderbuffer,
+    renderbuffer_storage: RenderbufferStorage,
+    image_target_renderbuffer_storage: ImageTargetRenderbufferStorage,
+    get_error: GetError,
+}
+
+impl Gl {
+    fn load(display: &Display) -> Option<Self> {
+        let load = |name: &[u8]| {
+            let addr = display.get_proc_address(CStr::from_bytes_with_nul(name).unwrap());
+            (!addr.is_null()).then_some(addr)
+        };
+
+        unsafe {
+            Some(Self {
+                gen_renderbuffers: mem::transmute::<*const c_void, GenRenderbuffers>(load(
+                    b"glGenRenderbuffers\0",
+                )?),
+                bind_renderbuffer: mem::transmute::<*const c_void, BindRenderbuffer>(load(
+                    b"glBindRenderbuffer\0",
+                )?),
+                renderbuffer_storage: mem::transmute::<*const c_void, RenderbufferStorage>(load(
+                    b"glRenderbufferStorage\0",
+                )?),
+                image_target_renderbuffer_storage: mem::transmute::<
+                    *const c_void,
+                    ImageTargetRenderbufferStorage,
+                >(load(
+                    b"glEGLImageTargetRenderbufferStorageOES\0",
+                )?),
+                get_error: mem::transmute::<*const c_void, GetError>(load(b"glGetError\0")?),
+            })
+        }
+    }
+
+    /// Create the renderbuffer with the storage allocated, leaving it bound.
+    fn create_renderbuffer(&self) -> u32 {
+        let mut renderbuffer = 0;
+        (self.gen_renderbuffers)(1, &mut renderbuffer);
+        (self.bind_renderbuffer)(GL_RENDERBUFFER, renderbuffer);
+        (self.renderbuffer_storage)(GL_RENDERBUFFER, GL_RGBA8, 16, 16);
+        renderbuffer
+    }
+}
+
+#[test]
+fn dropping_image_keeps_error_state_and_other_images() {
+    let Some((display, _, context)) = common::current_context(&common::gles_attributes()) else {
+        eprintln!("skipping, no EGL device supports the surfaceless GLES context");
+        return;
+    };
+    let Some(gl) = Gl::load(&display) else {
+        eprintln!("skipping, GL_OES_EGL_image is not supported");
+        return;
+    };
+
+    let egl = unsafe { Library::new("libEGL.so.1") }.unwrap();
+    let egl_get_error = unsafe { *egl.get::<EglGetError>(b"eglGetError\0").unwrap() };
+
+    let first = gl.create_renderbuffer();
+    let second = gl.create_renderbuffer();
+    let (first, second) = match (
+        context.create_image(GlImageSource::Renderbuffer(first)),
+        context.create_image(GlImageSource::Renderbuffer(second)),
+    ) {
+        (Ok(first), Ok(second)) => (first, second),
+        (Err(err), _) | (_, Err(err)) => {
+            eprintln!("skipping, failed to create the image: {err}");
+            return;
+        },
+    };
+
+    // Reset the error state before the drop.
+    egl_get_error();
+    drop(first);
+    assert_eq!(egl_get_error(), EGL_SUCCESS);
+
+    // The remaining image is still usable as the renderbuffer storage.
+    let target = gl.create_renderbuffer();
+    (gl.bind_renderbuffer)(GL_RENDERBUFFER, target);
+    (gl.get_error)();
+    (gl.image_target_renderbuffer_storage)(GL_RENDERBUFFER, second.raw_image());
+    assert_eq!((gl.get_error)(), GL_NO_ERROR);
+
+    drop(second);
+    assert_eq!(egl_get_error(), EGL_SUCCESS);
+}
@@ -0,0 +1,20 @@
+//! Tests of the WGL `get_proc_address`.
+
+#![cfg(wgl_backend)]
+
+use std::ffi::CStr;
+
+use glutin::api::wgl::display::Display;
+use glutin::prelude::*;
+use raw_window_handle::{RawDisplayHandle, WindowsDisplayHandle};
+
+#[test]
+fn opengl_1_1_functions_resolve() {
+    let display = RawDisplayHandle::Windows(WindowsDisplayHandle::new());
+    let display = unsafe { Display::new(display, None) }.unwrap();
+
+    // The `wglGetProcAddress` doesn't resolve the functions exported by the
+    // `opengl32.dll` itself.
+    let name = CStr::from_bytes_with_nul(b"glClear\0").unwrap();
+    assert!(display.get_proc_address_checked(name).is_some());
+}
@@ -39,13 +39,17 @@ fn main() {
             "EGL_KHR_create_context_no_error",
             "EGL_KHR_display_reference",
             "EGL_KHR_fence_sync",
+            "EGL_KHR_gl_renderbuffer_image",
+            "EGL_KHR_gl_texture_2D_image",
             "EGL_KHR_image_base",
             "EGL_KHR_platform_android",
             "EGL_KHR_platform_gbm",
             "EGL_KHR_platform_wayland",
             "EGL_KHR_platform_x11",
+            "EGL_KHR_reusable_sync",
             "EGL_KHR_swap_buffers_with_damage",
             "EGL_KHR_wait_sync",
+            "EGL_MESA_image_dma_buf_export",
             "EGL_MESA_platform_gbm",
         ]);
 
@@ -32,8 +32,57 @@ pub const PLATFORM_ANGLE_MAX_VERSION_MINOR_ANGLE: super::EGLenum = 0x3205;
 pub const PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED: super::EGLenum = 0x3451;
 pub const PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE: super::EGLenum = 0x348F;
 pub const PLATFORM_ANGLE_TYPE_DEFAULT_ANGLE: super::EGLenum = 0x3206;
+pub const PLATFORM_ANGLE_TYPE_D3D9_ANGLE: super::EGLenum = 0x3207;
+pub const PLATFORM_ANGLE_TYPE_D3D11_ANGLE: super::EGLenum = 0x3208;
+pub const PLATFORM_ANGLE_TYPE_OPENGL_ANGLE: super::EGLenum = 0x320D;
+pub const PLATFORM_ANGLE_TYPE_METAL_ANGLE: super::EGLenum = 0x3489;
 pub const PLATFORM_ANGLE_DEVICE_TYPE_HARDWARE_ANGLE: super::EGLenum = 0x320A;
 pub const PLATFORM_ANGLE_DEVICE_TYPE_NULL_ANGLE: super::EGLenum = 0x345E;
+// EGL_ANGLE_display_power_preference
+pub const POWER_PREFERENCE_ANGLE: super::EGLenum = 0x3482;
+pub const LOW_POWER_ANGLE: super::EGLenum = 0x0001;
+pub const HIGH_POWER_ANGLE: super::EGLenum = 0x0002;
+// EGL_ANGLE_surface_orientation
+pub const OPTIMAL_SURFACE_ORIENTATION_ANGLE: super::EGLenum = 0x33A7;
+pub const SURFACE_ORIENTATION_ANGLE: super::EGLenum = 0x33A8;
+pub const SURFACE_ORIENTATION_INVERT_X_ANGLE: super::EGLenum = 0x0001;
+pub const SURFACE_ORIENTATION_INVERT_Y_ANGLE: super::EGLenum = 0x0002;
+// EGL_KHR_context_flush_control
+pub const CONTEXT_RELEASE_BEHAVIOR_KHR: super::EGLenum = 0x2097;
+pub const CONTEXT_RELEASE_BEHAVIOR_NONE_KHR: super::EGLenum = 0x0000;
+pub const CONTEXT_RELEASE_BEHAVIOR_FLUSH_KHR: super::EGLenum = 0x2098;
+// EGL_EXT_image_dma_buf_import
+pub const LINUX_DMA_BUF_EXT: super::EGLenum = 0x3270;
+pub const LINUX_DRM_FOURCC_EXT: super::EGLenum = 0x3271;
+pub const DMA_BUF_PLANE0_FD_EXT: super::EGLenum = 0x3272;
+pub const DMA_BUF_PLANE0_OFFSET_EXT: super::EGLenum = 0x3273;
+pub const DMA_BUF_PLANE0_PITCH_EXT: super::EGLenum = 0x3274;
+pub const DMA_BUF_PLANE1_FD_EXT: super::EGLenum = 0x3275;
+pub const DMA_BUF_PLANE1_OFFSET_EXT: super::EGLenum = 0x3276;
+pub const DMA_BUF_PLANE1_PITCH_EXT: super::EGLenum = 0x3277;
+pub const DMA_BUF_PLANE2_FD_EXT: super::EGLenum = 0x3278;
+pub const DMA_BUF_PLANE2_OFFSET_EXT: super::EGLenum = 0x3279;
+pub const DMA_BUF_PLANE2_PITCH_EXT: super::EGLenum = 0x327A;
+// EGL_EXT_image_dma_buf_import_modifiers
+pub const DMA_BUF_PLANE3_FD_EXT: super::EGLenum = 0x3440;
+pub const DMA_BUF_PLANE3_OFFSET_EXT: super::EGLenum = 0x3441;
+pub const DMA_BUF_PLANE3_PITCH_EXT: super::EGLenum = 0x3442;
+pub const DMA_BUF_PLANE0_MODIFIER_LO_EXT: super::EGLenum = 0x3443;
+pub const DMA_BUF_PLANE0_MODIFIER_HI_EXT: super::EGLenum = 0x3444;
+pub const DMA_BUF_PLANE1_MODIFIER_LO_EXT: super::EGLenum = 0x3445;
+pub const DMA_BUF_PLANE1_MODIFIER_HI_EXT: super::EGLenum = 0x3446;
+pub const DMA_BUF_PLANE2_MODIFIER_LO_EXT: super::EGLenum = 0x3447;
+pub const DMA_BUF_PLANE2_MODIFIER_HI_EXT: super::EGLenum = 0x3448;
+pub const DMA_BUF_PLANE3_MODIFIER_LO_EXT: super::EGLenum = 0x3449;
+pub const DMA_BUF_PLANE3_MODIFIER_HI_EXT: super::EGLenum = 0x344A;
+// EGL_ANDROID_get_frame_timestamps
+pub const TIMESTAMPS_ANDROID: super::EGLenum = 0x3430;
+pub const REQUESTED_PRESENT_TIME_ANDROID: super::EGLenum = 0x3434;
+pub const RENDERING_COMPLETE_TIME_ANDROID: super::EGLenum = 0x3435;
+pub const COMPOSITION_LATCH_TIME_ANDROID: super::EGLenum = 0x3436;
+pub const DISPLAY_PRESENT_TIME_ANDROID: super::EGLenum = 0x343A;
+pub const TIMESTAMP_PENDING_ANDROID: super::EGLint = -2;
+pub const TIMESTAMP_INVALID_ANDROID: super::EGLint = -1;
 
 mod wayland_storage {
     use super::FnPtr;
@@ -228,6 +277,185 @@ pub mod QueryWaylandBufferWL {
     }
 }
 
+mod dma_buf_storage {
+    use super::FnPtr;
+    use super::__gl_imports::raw;
+
+    // EGL_EXT_image_dma_buf_import_modifiers
+    pub static mut QUERY_DMA_BUF_MODIFIERS_EXT: FnPtr =
+        FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
+}
+
+impl Egl {
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn QueryDmaBufModifiersEXT(
+        &self,
+        dpy: types::EGLDisplay,
+        format: types::EGLint,
+        max_modifiers: types::EGLint,
+        modifiers: *mut types::EGLuint64KHR,
+        external_only: *mut types::EGLBoolean,
+        num_modifiers: *mut types::EGLint,
+    ) -> types::EGLBoolean {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::EGLDisplay,
+                types::EGLint,
+                types::EGLint,
+                *mut types::EGLuint64KHR,
+                *mut types::EGLBoolean,
+                *mut types::EGLint,
+            ) -> types::EGLBoolean,
+        >(dma_buf_storage::QUERY_DMA_BUF_MODIFIERS_EXT.f)(
+            dpy,
+            format,
+            max_modifiers,
+            modifiers,
+            external_only,
+            num_modifiers,
+        )
+    }
+}
+
+// Extension: EGL_EXT_image_dma_buf_import_modifiers
+//
+
+#[allow(non_snake_case)]
+pub mod QueryDmaBufModifiersEXT {
+    use super::__gl_imports::raw;
+    use super::{dma_buf_storage, metaloadfn, FnPtr};
+
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_loaded() -> bool {
+        unsafe { dma_buf_storage::QUERY_DMA_BUF_MODIFIERS_EXT.is_loaded }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_with<F>(mut loadfn: F)
+    where
+        F: FnMut(&'static str) -> *const raw::c_void,
+    {
+        unsafe {
+            dma_buf_storage::QUERY_DMA_BUF_MODIFIERS_EXT =
+                FnPtr::new(metaloadfn(&mut loadfn, "eglQueryDmaBufModifiersEXT", &[]))
+        }
+    }
+}
+
+mod frame_timestamps_storage {
+    use super::FnPtr;
+    use super::__gl_imports::raw;
+
+    // EGL_ANDROID_get_frame_timestamps
+    pub static mut GET_NEXT_FRAME_ID_ANDROID: FnPtr =
+        FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
+    pub static mut GET_FRAME_TIMESTAMPS_ANDROID: FnPtr =
+        FnPtr { f: super::missing_fn_panic as *const raw::c_void, is_loaded: false };
+}
+
+impl Egl {
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn GetNextFrameIdANDROID(
+        &self,
+        dpy: types::EGLDisplay,
+        surface: types::EGLSurface,
+        frame_id: *mut types::EGLuint64KHR,
+    ) -> types::EGLBoolean {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::EGLDisplay,
+                types::EGLSurface,
+                *mut types::EGLuint64KHR,
+            ) -> types::EGLBoolean,
+        >(frame_timestamps_storage::GET_NEXT_FRAME_ID_ANDROID.f)(dpy, surface, frame_id)
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn GetFrameTimestampsANDROID(
+        &self,
+        dpy: types::EGLDisplay,
+        surface: types::EGLSurface,
+        frame_id: types::EGLuint64KHR,
+        num_timestamps: types::EGLint,
+        timestamps: *const types::EGLint,
+        values: *mut i64,
+    ) -> types::EGLBoolean {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::EGLDisplay,
+                types::EGLSurface,
+                types::EGLuint64KHR,
+                types::EGLint,
+                *const types::EGLint,
+                *mut i64,
+            ) -> types::EGLBoolean,
+        >(frame_timestamps_storage::GET_FRAME_TIMESTAMPS_ANDROID.f)(
+            dpy,
+            surface,
+            frame_id,
+            num_timestamps,
+            timestamps,
+            values,
+        )
+    }
+}
+
+// Extension: EGL_ANDROID_get_frame_timestamps
+//
+
+#[allow(non_snake_case)]
+pub mod GetNextFrameIdANDROID {
+    use super::__gl_imports::raw;
+    use super::{frame_timestamps_storage, metaloadfn, FnPtr};
+
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_loaded() -> bool {
+        unsafe { frame_timestamps_storage::GET_NEXT_FRAME_ID_ANDROID.is_loaded }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_with<F>(mut loadfn: F)
+    where
+        F: FnMut(&'static str) -> *const raw::c_void,
+    {
+        unsafe {
+            frame_timestamps_storage::GET_NEXT_FRAME_ID_ANDROID =
+                FnPtr::new(metaloadfn(&mut loadfn, "eglGetNextFrameIdANDROID", &[]))
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+pub mod GetFrameTimestampsANDROID {
+    use super::__gl_imports::raw;
+    use super::{frame_timestamps_storage, metaloadfn, FnPtr};
+
+    #[inline]
+    #[allow(dead_code)]
+    pub fn is_loaded() -> bool {
+        unsafe { frame_timestamps_storage::GET_FRAME_TIMESTAMPS_ANDROID.is_loaded }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_with<F>(mut loadfn: F)
+    where
+        F: FnMut(&'static str) -> *const raw::c_void,
+    {
+        unsafe {
+            frame_timestamps_storage::GET_FRAME_TIMESTAMPS_ANDROID =
+                FnPtr::new(metaloadfn(&mut loadfn, "eglGetFrameTimestampsANDROID", &[]))
+        }
+    }
+}
+
 /// OpenGL function loader.
 ///
 /// This is based on the loader generated by `gl_generator`.
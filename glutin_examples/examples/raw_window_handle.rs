@@ -0,0 +1,92 @@
+fn main() {
+    #[cfg(all(egl_backend, x11_platform))]
+    example::run();
+}
+
+/// Render into the window created with plain Xlib, showing that glutin only
+/// needs the raw handles, so any windowing library, like SDL2, could be used.
+#[cfg(all(egl_backend, x11_platform))]
+mod example {
+    use std::num::NonZeroU32;
+    use std::ptr::NonNull;
+    use std::time::{Duration, Instant};
+
+    use glutin::config::ConfigTemplateBuilder;
+    use glutin::context::ContextAttributesBuilder;
+    use glutin::display::{Display, DisplayApiPreference};
+    use glutin::prelude::*;
+    use glutin::surface::{SurfaceAttributesBuilder, WindowSurface};
+    use glutin_examples::Renderer;
+    use raw_window_handle::{
+        RawDisplayHandle, RawWindowHandle, XlibDisplayHandle, XlibWindowHandle,
+    };
+    use x11_dl::xlib::Xlib;
+
+    const WIDTH: u32 = 640;
+    const HEIGHT: u32 = 480;
+
+    pub fn run() {
+        let xlib = Xlib::open().expect("failed to load Xlib");
+
+        // Create the window with whatever windowing library the application uses.
+        let (xdisplay, screen, xwindow) = unsafe {
+            let xdisplay = (xlib.XOpenDisplay)(std::ptr::null());
+            assert!(!xdisplay.is_null(), "failed to open X display");
+            let screen = (xlib.XDefaultScreen)(xdisplay);
+            let root = (xlib.XRootWindow)(xdisplay, screen);
+            let xwindow = (xlib.XCreateSimpleWindow)(xdisplay, root, 0, 0, WIDTH, HEIGHT, 0, 0, 0);
+            (xlib.XMapWindow)(xdisplay, xwindow);
+            (xlib.XFlush)(xdisplay);
+            (xdisplay, screen, xwindow)
+        };
+
+        // Everything glutin needs are the raw handles.
+        let raw_display_handle =
+            RawDisplayHandle::Xlib(XlibDisplayHandle::new(NonNull::new(xdisplay.cast()), screen));
+        let raw_window_handle = RawWindowHandle::Xlib(XlibWindowHandle::new(xwindow));
+
+        let gl_display = unsafe { Display::new(raw_display_handle, DisplayApiPreference::Egl) }
+            .expect("failed to create display");
+
+        // The visual of the window is used to pick the compatible config.
+        let template =
+            ConfigTemplateBuilder::new().compatible_with_native_window(raw_window_handle);
+        let gl_config = unsafe { gl_display.find_configs(template.build()) }
+            .unwrap()
+            .next()
+            .expect("no config matches the window");
+
+        let context_attributes = ContextAttributesBuilder::new().build(Some(raw_window_handle));
+        let gl_context = unsafe { gl_display.create_context(&gl_config, &context_attributes) }
+            .expect("failed to create context");
+
+        let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new().build(
+            raw_window_handle,
+            NonZeroU32::new(WIDTH).unwrap(),
+            NonZeroU32::new(HEIGHT).unwrap(),
+        );
+        let gl_surface =
+            unsafe { gl_display.create_window_surface(&gl_config, &surface_attributes) }
+                .expect("failed to create surface");
+
+        let gl_context = gl_context.make_current(&gl_surface).unwrap();
+
+        let renderer = Renderer::new(&gl_display);
+        renderer.resize(WIDTH as i32, HEIGHT as i32);
+
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            renderer.draw();
+            gl_surface.swap_buffers(&gl_context).unwrap();
+        }
+
+        // Destroy the GL objects before the window they render into.
+        drop(renderer);
+        drop(gl_surface);
+        drop(gl_context);
+        unsafe {
+            (xlib.XDestroyWindow)(xdisplay, xwindow);
+            (xlib.XCloseDisplay)(xdisplay);
+        }
+    }
+}
@@ -11,7 +11,7 @@ use winit::event_loop::ActiveEventLoop;
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Window, WindowAttributes};
 
-use glutin::config::{Config, ConfigTemplateBuilder, GetGlConfig};
+use glutin::config::{Config, ConfigScorer, ConfigTemplateBuilder, GetGlConfig};
 use glutin::context::{
     ContextApi, ContextAttributesBuilder, NotCurrentContext, PossiblyCurrentContext, Version,
 };
@@ -177,8 +177,11 @@ impl ApplicationHandler for App {
         #[cfg(egl_backend)]
         #[allow(irrefutable_let_patterns)]
         if let glutin::display::Display::Egl(display) = _gl_display {
-            unsafe {
-                display.terminate();
+            let quirks = display.driver_quirks();
+            if quirks.contains(glutin::display::DriverQuirks::NVIDIA_WAYLAND_TERMINATE_ON_EXIT) {
+                unsafe {
+                    display.terminate();
+                }
             }
         }
     }
@@ -218,17 +221,14 @@ fn create_gl_context(window: &Window, gl_config: &Config) -> NotCurrentContext {
     // has to be created.
     let gl_display = gl_config.display();
 
-    unsafe {
-        gl_display.create_context(gl_config, &context_attributes).unwrap_or_else(|_| {
-            gl_display.create_context(gl_config, &fallback_context_attributes).unwrap_or_else(
-                |_| {
-                    gl_display
-                        .create_context(gl_config, &legacy_context_attributes)
-                        .expect("failed to create context")
-                },
-            )
-        })
-    }
+    let attributes = [context_attributes, fallback_context_attributes, legacy_context_attributes];
+    let (context, _) = unsafe {
+        gl_display
+            .create_context_with_fallbacks(gl_config, &attributes)
+            .expect("failed to create context")
+    };
+
+    context
 }
 
 fn window_attributes() -> WindowAttributes {
@@ -277,18 +277,7 @@ struct AppState {
 // Find the config with the maximum number of samples, so our triangle will be
 // smooth.
 pub fn gl_config_picker(configs: Box<dyn Iterator<Item = Config> + '_>) -> Config {
-    configs
-        .reduce(|accum, config| {
-            let transparency_check = config.supports_transparency().unwrap_or(false)
-                & !accum.supports_transparency().unwrap_or(false);
-
-            if transparency_check || config.num_samples() > accum.num_samples() {
-                config
-            } else {
-                accum
-            }
-        })
-        .unwrap()
+    ConfigScorer::new().with_multisampling(true).with_transparency(true).pick_best(configs).unwrap()
 }
 
 pub struct Renderer {
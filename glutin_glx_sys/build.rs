@@ -35,7 +35,9 @@ fn main() {
             "GLX_EXT_framebuffer_sRGB",
             "GLX_EXT_swap_control",
             "GLX_MESA_swap_control",
+            "GLX_OML_sync_control",
             "GLX_SGI_swap_control",
+            "GLX_SGI_video_sync",
         ])
         .write_bindings(gl_generator::StructGenerator, &mut file)
         .unwrap();
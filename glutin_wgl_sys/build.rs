@@ -31,6 +31,8 @@ fn main() {
             "WGL_EXT_extensions_string",
             "WGL_EXT_framebuffer_sRGB",
             "WGL_EXT_swap_control",
+            "WGL_NV_DX_interop",
+            "WGL_NV_DX_interop2",
         ])
         .write_bindings(gl_generator::StructGenerator, &mut file)
         .unwrap();